@@ -2,3 +2,15 @@
 pub trait Act<X> {
     fn act(&self, x: X) -> X;
 }
+
+/// Act which may fail on a (product) value, e.g. Segment Tree Beats;
+/// on failure, the act is pushed down and the value is recomputed from the children.
+/// Acts on single items should never fail.
+pub trait FailableAct<X> {
+    fn try_act(&self, x: X) -> Option<X>;
+}
+impl<X, M: Act<X>> FailableAct<X> for M {
+    fn try_act(&self, x: X) -> Option<X> {
+        Some(self.act(x))
+    }
+}
@@ -0,0 +1,324 @@
+/// Algebra - Formal Power Series on ModInt (mod 998244353)
+use crate::agroup; // IGNORE
+use crate::algebra::group_additive::*;
+use crate::algebra::modint::*;
+use crate::algebra::monoid::*;
+use crate::algebra::ring::*;
+use crate::algorithm::ntt::*;
+use crate::mint; // IGNORE
+use crate::monoid; // IGNORE
+use crate::ring; // IGNORE
+
+/// f(x) = sum_i self.0[i] x^i
+/// The modulo should be MOD_998244353 (for NTT).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormalPowerSeries(pub Vec<ModInt>);
+
+impl FormalPowerSeries {
+    pub fn new(coeffs: Vec<ModInt>) -> Self {
+        Self(coeffs)
+    }
+    pub fn from(coeffs: &[i128]) -> Self {
+        Self(coeffs.iter().map(|&c| mint!(c)).collect())
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Coefficient of x^i
+    pub fn at(&self, i: usize) -> ModInt {
+        if i < self.0.len() {
+            self.0[i]
+        } else {
+            mint!(0)
+        }
+    }
+    /// f mod x^n (with zero-padding)
+    pub fn prefix(&self, n: usize) -> Self {
+        Self((0..n).map(|i| self.at(i)).collect())
+    }
+    /// x^k * f
+    fn shift(&self, k: usize) -> Self {
+        Self(
+            (0..k)
+                .map(|_| mint!(0))
+                .chain(self.0.iter().cloned())
+                .collect(),
+        )
+    }
+    /// f * g mod x^n
+    pub fn mul_trunc(&self, other: &Self, n: usize) -> Self {
+        let f = self.prefix(std::cmp::min(n, self.len()));
+        let g = other.prefix(std::cmp::min(n, other.len()));
+        (f * g).prefix(n)
+    }
+    pub fn derivative(&self) -> Self {
+        Self((1..self.len()).map(|i| self.0[i] * i as i128).collect())
+    }
+    /// Integral with constant term 0
+    pub fn integral(&self) -> Self {
+        let n = self.len();
+        let mut invs = vec![mint!(1); n + 1];
+        for i in 2..=n {
+            invs[i] = -invs[MOD_998244353 as usize % i] * (MOD_998244353 / i as i128);
+        }
+        Self(
+            std::iter::once(mint!(0))
+                .chain((0..n).map(|i| self.0[i] * invs[i + 1]))
+                .collect(),
+        )
+    }
+    /// 1/f mod x^n, requires f[0] != 0
+    pub fn inv(&self, n: usize) -> Self {
+        assert!(self.at(0).0 != 0, "Constant term should be non-zero");
+        let mut g = Self(vec![self.at(0).inv()]);
+        let mut k = 1;
+        while k < n {
+            k *= 2;
+            let h = FormalPowerSeries::from(&[2]) - self.mul_trunc(&g, k);
+            g = g.mul_trunc(&h, k);
+        }
+        g.prefix(n)
+    }
+    /// log(f) mod x^n, requires f[0] == 1
+    pub fn log(&self, n: usize) -> Self {
+        assert!(self.at(0) == mint!(1), "Constant term should be 1");
+        self.prefix(n + 1)
+            .derivative()
+            .mul_trunc(&self.inv(n), n)
+            .integral()
+            .prefix(n)
+    }
+    /// exp(f) mod x^n, requires f[0] == 0
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(self.at(0).0 == 0, "Constant term should be 0");
+        let mut g = Self::one();
+        let mut k = 1;
+        while k < n {
+            k *= 2;
+            let h = self.prefix(k) - g.log(k) + Self::one();
+            g = g.mul_trunc(&h, k);
+        }
+        g.prefix(n)
+    }
+    /// f^k mod x^n
+    pub fn pow(&self, k: u64, n: usize) -> Self {
+        if k == 0 {
+            return Self::one().prefix(n);
+        }
+        let i = match self.0.iter().position(|c| c.0 != 0) {
+            Some(i) if (i as u128) * (k as u128) < n as u128 => i,
+            _ => return Self::zero().prefix(n),
+        };
+        let m = n - i * k as usize;
+        let c = self.0[i];
+        let f = Self(self.0[i..].to_vec()) * c.inv();
+        let g = (f.log(m) * mint!(k as i128)).exp(m) * c.pow(k as i128);
+        g.shift(i * k as usize)
+    }
+    /// g s.t. g * g = f mod x^n, if exists
+    pub fn sqrt(&self, n: usize) -> Option<Self> {
+        let i = match self.0.iter().position(|c| c.0 != 0) {
+            Some(i) => i,
+            None => return Some(Self::zero().prefix(n)),
+        };
+        if i / 2 >= n {
+            return Some(Self::zero().prefix(n));
+        }
+        if i % 2 == 1 {
+            // f = 0 mod x^n has the trivial root
+            return if i >= n {
+                Some(Self::zero().prefix(n))
+            } else {
+                None
+            };
+        }
+        let m = n - i / 2;
+        let f = Self(self.0[i..].to_vec());
+        let mut g = Self(vec![sqrt_modint(f.0[0])?]);
+        let inv2 = mint!(2).inv();
+        let mut k = 1;
+        while k < m {
+            k *= 2;
+            g = (g.clone() + f.mul_trunc(&g.inv(k), k)) * inv2;
+        }
+        Some(g.prefix(m).shift(i / 2))
+    }
+}
+
+/// Tonelli-Shanks; x s.t. x * x == a (modulo should be an odd prime)
+fn sqrt_modint(a: ModInt) -> Option<ModInt> {
+    let p = a.1;
+    if a.0 == 0 {
+        return Some(a);
+    }
+    if a.pow((p - 1) / 2).0 != 1 {
+        return None;
+    }
+    let mut q = p - 1;
+    let mut s = 0;
+    while q % 2 == 0 {
+        q /= 2;
+        s += 1;
+    }
+    let mut z = ModInt(2, p);
+    while z.pow((p - 1) / 2).0 == 1 {
+        z += 1;
+    }
+    let mut c = z.pow(q);
+    let mut t = a.pow(q);
+    let mut r = a.pow((q + 1) / 2);
+    let mut m = s;
+    while t.0 != 1 {
+        let mut i = 0;
+        let mut tt = t;
+        while tt.0 != 1 {
+            tt *= tt;
+            i += 1;
+        }
+        let b = c.pow(1 << (m - i - 1));
+        m = i;
+        c = b * b;
+        t *= c;
+        r *= b;
+    }
+    Some(r)
+}
+
+agroup! {
+    FormalPowerSeries;
+    zero = FormalPowerSeries(vec![]);
+    add(self, other) = {
+        let n = std::cmp::max(self.len(), other.len());
+        FormalPowerSeries((0..n).map(|i| self.at(i) + other.at(i)).collect())
+    };
+    neg(self) = {
+        FormalPowerSeries(self.0.iter().map(|&c| -c).collect())
+    };
+}
+monoid! {
+    FormalPowerSeries;
+    one = FormalPowerSeries(vec![mint!(1)]);
+    mul(self, other) = {
+        FormalPowerSeries(convolve(&self.0, &other.0))
+    };
+}
+ring! {
+    FormalPowerSeries;
+    div(self, other) = {
+        let n = self.len();
+        self.mul_trunc(&other.inv(n), n)
+    };
+}
+impl std::ops::Mul<ModInt> for FormalPowerSeries {
+    type Output = Self;
+    fn mul(self, c: ModInt) -> Self {
+        FormalPowerSeries(self.0.iter().map(|&x| x * c).collect())
+    }
+}
+
+#[cfg(test)]
+mod test_formal_power_series {
+    use crate::algebra::formal_power_series::*;
+
+    fn coeffs(f: &FormalPowerSeries) -> Vec<i128> {
+        f.0.iter().map(|c| c.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_ring() {
+        let f = FormalPowerSeries::from(&[1, 2, 3]);
+        let g = FormalPowerSeries::from(&[4, 5]);
+        assert_eq!(coeffs(&(f.clone() + g.clone())), vec![5, 7, 3]);
+        assert_eq!(f.clone() - g.clone(), FormalPowerSeries::from(&[-3, -3, 3]));
+        assert_eq!(coeffs(&(f.clone() * g.clone())), vec![4, 13, 22, 15]);
+        assert_eq!(coeffs(&f.mul_trunc(&g, 2)), vec![4, 13]);
+        assert_eq!(f.clone() * FormalPowerSeries::one(), f);
+        assert_eq!(f.clone() + FormalPowerSeries::zero(), f);
+        assert_eq!((f.clone() * g.clone()) / g, f.prefix(4));
+    }
+
+    #[test]
+    fn test_derivative_integral() {
+        let f = FormalPowerSeries::from(&[1, 2, 3, 4]);
+        assert_eq!(coeffs(&f.derivative()), vec![2, 6, 12]);
+        assert_eq!(
+            f.derivative().integral(),
+            FormalPowerSeries::from(&[0, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn test_inv() {
+        let f = FormalPowerSeries::from(&[5, 4, 3, 2, 1]);
+        for n in 1..20 {
+            let g = f.inv(n);
+            assert_eq!(g.len(), n);
+            assert_eq!(f.mul_trunc(&g, n), FormalPowerSeries::one().prefix(n));
+        }
+        // 1 / (1 - x) = 1 + x + x^2 + ...
+        let f = FormalPowerSeries::from(&[1, -1]);
+        assert_eq!(coeffs(&f.inv(5)), vec![1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_log_exp() {
+        // exp(x) = sum x^k / k!
+        let e = FormalPowerSeries::from(&[0, 1]).exp(6);
+        let mut fact = mint!(1);
+        for k in 0..6 {
+            assert_eq!(e.at(k) * fact, mint!(1));
+            fact *= (k + 1) as i128;
+        }
+        let f = FormalPowerSeries::from(&[1, 3, 1, 4, 1, 5, 9, 2, 6]);
+        for n in 1..20 {
+            assert_eq!(f.log(n).exp(n), f.prefix(n));
+        }
+        // log(1 / (1 - x)) = sum x^k / k
+        let g = FormalPowerSeries::from(&[1, -1]).inv(10).log(10);
+        for k in 1..10 {
+            assert_eq!(g.at(k) * k as i128, mint!(1));
+        }
+    }
+
+    #[test]
+    fn test_pow() {
+        let f = FormalPowerSeries::from(&[0, 0, 2, 3, 1]);
+        for k in 0..5 {
+            let mut g = FormalPowerSeries::one();
+            for _ in 0..k {
+                g *= f.clone();
+            }
+            for n in 0..20 {
+                assert_eq!(f.pow(k, n), g.prefix(n));
+            }
+        }
+        assert_eq!(
+            f.pow(1_000_000_000_000, 10),
+            FormalPowerSeries::zero().prefix(10)
+        );
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let g = FormalPowerSeries::from(&[0, 3, 1, 4, 1, 5]);
+        let f = g.clone() * g.clone();
+        for n in 1..12 {
+            let h = f.sqrt(n).unwrap();
+            assert_eq!(h.mul_trunc(&h, n), f.prefix(n));
+        }
+        assert_eq!(FormalPowerSeries::from(&[0, 1]).sqrt(4), None);
+        // the lowest term has odd order, but vanishes mod x^n
+        let f = FormalPowerSeries::from(&[0, 0, 0, 1, 2]);
+        assert_eq!(f.sqrt(1), Some(FormalPowerSeries::from(&[0])));
+        assert_eq!(f.sqrt(2), Some(FormalPowerSeries::from(&[0, 0])));
+        assert_eq!(f.sqrt(3), Some(FormalPowerSeries::from(&[0, 0, 0])));
+        assert_eq!(f.sqrt(4), None);
+        assert_eq!(
+            FormalPowerSeries::zero().sqrt(3),
+            Some(FormalPowerSeries::from(&[0, 0, 0]))
+        );
+    }
+}
@@ -3,11 +3,15 @@ pub mod act_add;
 pub mod act_assign;
 pub mod complex;
 pub mod field;
+pub mod formal_power_series;
 pub mod group;
 pub mod group_additive;
 pub mod hyper;
 pub mod matrix;
 pub mod modint;
+pub mod modint_dynamic;
+pub mod modint_like;
+pub mod modint_static;
 pub mod module;
 pub mod monoid;
 pub mod monoid_max;
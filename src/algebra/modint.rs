@@ -2,6 +2,7 @@
 use crate::agroup; // IGNORE
 use crate::algebra::field::*;
 use crate::algebra::group_additive::*;
+use crate::algebra::modint_like::*;
 use crate::algebra::monoid::*;
 use crate::algebra::ring::*;
 use crate::mint; // IGNORE
@@ -89,6 +90,17 @@ ring! {
     div(self, other) = { self * other.inv() };
 }
 impl Field for ModInt {}
+impl ModIntLike for ModInt {
+    fn like(self, x: i128) -> Self {
+        ModInt::new(x, self.1)
+    }
+    fn residual(self) -> i128 {
+        self.0
+    }
+    fn modulo(self) -> i128 {
+        self.1
+    }
+}
 
 impl std::ops::Add<i128> for ModInt {
     type Output = Self;
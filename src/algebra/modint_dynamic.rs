@@ -0,0 +1,281 @@
+/// Algebra - ModInt with Dynamic Modulus (Barrett Reduction)
+use crate::agroup; // IGNORE
+use crate::algebra::field::*;
+use crate::algebra::group_additive::*;
+use crate::algebra::modint_like::*;
+use crate::algebra::monoid::*;
+use crate::algebra::ring::*;
+use crate::monoid; // IGNORE
+use crate::ring; // IGNORE
+
+/// Barrett reduction for a runtime modulo m (1 <= m < 2^31)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Barrett {
+    m: u32,
+    im: u64, // ceil(2^64 / m)
+}
+impl Barrett {
+    pub fn new(m: u32) -> Self {
+        assert!((1..1 << 31).contains(&m));
+        let im = (u64::MAX / m as u64).wrapping_add(1);
+        Self { m, im }
+    }
+    pub fn modulo(&self) -> u32 {
+        self.m
+    }
+    /// a * b mod m, for a, b < m
+    pub fn mul(&self, a: u32, b: u32) -> u32 {
+        let z = a as u64 * b as u64;
+        let x = ((z as u128 * self.im as u128) >> 64) as u64;
+        let y = x.wrapping_mul(self.m as u64);
+        (z.wrapping_sub(y) as u32).wrapping_add(if z < y { self.m } else { 0 })
+    }
+}
+
+thread_local! {
+    static DYNAMIC_MODULO: std::cell::Cell<Barrett> = std::cell::Cell::new(Barrett::new(998_244_353));
+}
+
+/// Z/mZ, where m is set per thread by `DynamicModInt::set_modulo` (default: 998244353).
+/// Values made before `set_modulo` should not be used after that.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynamicModInt(u32);
+
+impl DynamicModInt {
+    pub fn set_modulo(m: u32) {
+        DYNAMIC_MODULO.with(|b| b.set(Barrett::new(m)));
+    }
+    fn barrett() -> Barrett {
+        DYNAMIC_MODULO.with(|b| b.get())
+    }
+    pub fn modulo() -> u32 {
+        Self::barrett().modulo()
+    }
+    pub fn new(x: i64) -> Self {
+        Self(x.rem_euclid(Self::modulo() as i64) as u32)
+    }
+    pub fn unwrap(self) -> u32 {
+        self.0
+    }
+    pub fn inv(self) -> Self {
+        let m = Self::modulo();
+        let (mut r0, mut r1) = (m as i64, self.0 as i64);
+        let (mut a0, mut a1) = (0_i64, 1_i64);
+        while r1 > 0 {
+            let q = r0 / r1;
+            let (r, a) = (r0 - q * r1, a0 - q * a1);
+            r0 = r1;
+            r1 = r;
+            a0 = a1;
+            a1 = a;
+        }
+        if r0 != 1 {
+            panic!("{} has no inverse mod {}!", self, m);
+        }
+        Self::new(a0)
+    }
+    pub fn pow(self, n: i64) -> Self {
+        if n < 0 {
+            return self.pow(-n).inv();
+        }
+        let b = Self::barrett();
+        let mut x = self.0;
+        let mut n = n;
+        let mut r = 1 % b.modulo();
+        while n > 0 {
+            if n & 1 == 1 {
+                r = b.mul(r, x);
+            }
+            x = b.mul(x, x);
+            n >>= 1;
+        }
+        Self(r)
+    }
+}
+impl std::fmt::Display for DynamicModInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl std::fmt::Debug for DynamicModInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} (mod {})", self.0, Self::modulo())
+    }
+}
+agroup! {
+    DynamicModInt;
+    zero = DynamicModInt(0);
+    add(self, other) = {
+        let m = Self::modulo() as u64;
+        let s = self.0 as u64 + other.0 as u64;
+        DynamicModInt(if s >= m { s - m } else { s } as u32)
+    };
+    neg(self) = {
+        DynamicModInt(if self.0 == 0 { 0 } else { Self::modulo() - self.0 })
+    };
+}
+monoid! {
+    DynamicModInt;
+    one = DynamicModInt::new(1);
+    mul(self, other) = {
+        DynamicModInt(Self::barrett().mul(self.0, other.0))
+    };
+}
+ring! {
+    DynamicModInt;
+    div(self, other) = { std::ops::Mul::mul(self, other.inv()) };
+}
+impl Field for DynamicModInt {}
+impl ModIntLike for DynamicModInt {
+    fn like(self, x: i128) -> Self {
+        Self::new(x.rem_euclid(Self::modulo() as i128) as i64)
+    }
+    fn residual(self) -> i128 {
+        self.0 as i128
+    }
+    fn modulo(self) -> i128 {
+        Self::modulo() as i128
+    }
+}
+impl From<i64> for DynamicModInt {
+    fn from(x: i64) -> Self {
+        Self::new(x)
+    }
+}
+
+impl std::ops::Add<i64> for DynamicModInt {
+    type Output = Self;
+    fn add(self, other: i64) -> Self {
+        self + Self::new(other)
+    }
+}
+impl std::ops::Add<DynamicModInt> for i64 {
+    type Output = DynamicModInt;
+    fn add(self, other: DynamicModInt) -> DynamicModInt {
+        other + self
+    }
+}
+impl std::ops::AddAssign<i64> for DynamicModInt {
+    fn add_assign(&mut self, other: i64) {
+        *self = *self + other;
+    }
+}
+impl std::ops::Sub<i64> for DynamicModInt {
+    type Output = Self;
+    fn sub(self, other: i64) -> Self {
+        self - Self::new(other)
+    }
+}
+impl std::ops::Sub<DynamicModInt> for i64 {
+    type Output = DynamicModInt;
+    fn sub(self, other: DynamicModInt) -> DynamicModInt {
+        DynamicModInt::new(self) - other
+    }
+}
+impl std::ops::SubAssign<i64> for DynamicModInt {
+    fn sub_assign(&mut self, other: i64) {
+        *self = *self - other;
+    }
+}
+impl std::ops::Mul<i64> for DynamicModInt {
+    type Output = Self;
+    fn mul(self, other: i64) -> Self {
+        self * Self::new(other)
+    }
+}
+impl std::ops::Mul<DynamicModInt> for i64 {
+    type Output = DynamicModInt;
+    fn mul(self, other: DynamicModInt) -> DynamicModInt {
+        other * self
+    }
+}
+impl std::ops::MulAssign<i64> for DynamicModInt {
+    fn mul_assign(&mut self, other: i64) {
+        *self = *self * other;
+    }
+}
+impl std::ops::Div<i64> for DynamicModInt {
+    type Output = Self;
+    fn div(self, other: i64) -> Self {
+        self / Self::new(other)
+    }
+}
+impl std::ops::Div<DynamicModInt> for i64 {
+    type Output = DynamicModInt;
+    fn div(self, other: DynamicModInt) -> DynamicModInt {
+        DynamicModInt::new(self) / other
+    }
+}
+impl std::ops::DivAssign<i64> for DynamicModInt {
+    fn div_assign(&mut self, other: i64) {
+        *self = *self / other;
+    }
+}
+
+#[cfg(test)]
+mod test_modint_dynamic {
+    use crate::algebra::modint_dynamic::*;
+
+    type Mint = DynamicModInt;
+
+    #[test]
+    fn test_barrett() {
+        for &m in &[1_u32, 2, 7, 998_244_353, 1_000_000_007, (1 << 31) - 1] {
+            let b = Barrett::new(m);
+            for &(x, y) in &[(0_u64, 0_u64), (1, 1), (12345, 67890), (1 << 31, 3)] {
+                let (x, y) = (x % m as u64, y % m as u64);
+                assert_eq!(b.mul(x as u32, y as u32) as u64, x * y % m as u64);
+            }
+            let x = m - 1;
+            assert_eq!(b.mul(x, x) as u64, (x as u64 * x as u64) % m as u64);
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        Mint::set_modulo(1_000_000_007);
+        assert_eq!(Mint::modulo(), 1_000_000_007);
+        assert_eq!(Mint::new(-1).unwrap(), 1_000_000_006);
+        assert_eq!(Mint::new(-1) * Mint::new(-1), Mint::one());
+        assert_eq!(Mint::new(1) - 3, Mint::new(-2));
+        assert_eq!((Mint::new(1) / -3) * 6, Mint::new(-2));
+        assert_eq!(Mint::new(2).pow(30), Mint::new(73_741_817));
+        assert_eq!(Mint::new(2).pow(-1) * 2, Mint::one());
+        Mint::set_modulo(13);
+        assert_eq!(Mint::new(100), Mint::new(9));
+        assert_eq!(Mint::new(7) + Mint::new(8), Mint::new(2));
+        let mut m = Mint::new(5);
+        m *= 3;
+        m -= 1;
+        assert_eq!(m, Mint::one());
+    }
+
+    #[test]
+    fn test_matrix() {
+        use crate::algebra::matrix::*;
+        Mint::set_modulo(10);
+        let fib = Matrix::new(vec![
+            vec![Mint::one(), Mint::one()],
+            vec![Mint::one(), Mint::zero()],
+        ]);
+        // fib(11) = 89, fib(10) = 55
+        assert_eq!(
+            fib.pow(10),
+            Matrix::new(vec![
+                vec![Mint::new(9), Mint::new(5)],
+                vec![Mint::new(5), Mint::new(4)],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_segment_tree() {
+        use crate::sequence::tree::segment_tree::*;
+        Mint::set_modulo(1_000_003);
+        let xs: Vec<Mint> = (1..=10).map(Mint::new).collect();
+        let mut st = SegmentTree::from(xs);
+        assert_eq!(st.product(0..10), Mint::new(3_628_800));
+        st.update(0, Mint::new(1_000_000));
+        assert_eq!(st.product(0..2), Mint::new(-6));
+    }
+}
@@ -0,0 +1,10 @@
+/// Algebra - ModInt-like (Z/mZ knowing its own modulo)
+use crate::algebra::field::*;
+
+pub trait ModIntLike: Field + std::ops::Div<Output = Self> + Copy + Eq {
+    /// x (mod m) on the same Z/mZ as self
+    fn like(self, x: i128) -> Self;
+    /// Representative in [0, m)
+    fn residual(self) -> i128;
+    fn modulo(self) -> i128;
+}
@@ -0,0 +1,277 @@
+/// Algebra - ModInt with Static Modulus (Montgomery Multiplication)
+use crate::agroup; // IGNORE
+use crate::algebra::field::*;
+use crate::algebra::group_additive::*;
+use crate::algebra::modint_like::*;
+use crate::algebra::monoid::*;
+use crate::algebra::ring::*;
+use crate::monoid; // IGNORE
+use crate::ring; // IGNORE
+
+/// Z/MZ, where M is odd and M < 2^31.
+/// Values are kept in Montgomery form (x * 2^32 mod M).
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticModInt<const M: u32>(u32);
+pub type ModInt998244353 = StaticModInt<998_244_353>;
+pub type ModInt1000000007 = StaticModInt<1_000_000_007>;
+
+impl<const M: u32> StaticModInt<M> {
+    /// -M^{-1} mod 2^32
+    const NEG_INV: u32 = {
+        assert!(M % 2 == 1 && M < 1 << 31, "M should be odd and < 2^31");
+        let mut inv = M;
+        let mut i = 0;
+        while i < 4 {
+            inv = inv.wrapping_mul(2_u32.wrapping_sub(M.wrapping_mul(inv)));
+            i += 1;
+        }
+        inv.wrapping_neg()
+    };
+    /// 2^64 mod M
+    const R2: u32 = ((1_u128 << 64) % M as u128) as u32;
+    /// t * 2^-32 mod M, for t < M * 2^32
+    fn reduce(t: u64) -> u32 {
+        let m = (t as u32).wrapping_mul(Self::NEG_INV);
+        let u = ((t + m as u64 * M as u64) >> 32) as u32;
+        if u >= M {
+            u - M
+        } else {
+            u
+        }
+    }
+    pub fn new(x: i64) -> Self {
+        let r = x.rem_euclid(M as i64) as u64;
+        Self(Self::reduce(r * Self::R2 as u64))
+    }
+    pub fn modulo() -> u32 {
+        M
+    }
+    pub fn unwrap(self) -> u32 {
+        Self::reduce(self.0 as u64)
+    }
+    pub fn inv(self) -> Self {
+        let (mut r0, mut r1) = (M as i64, self.unwrap() as i64);
+        let (mut a0, mut a1) = (0_i64, 1_i64);
+        while r1 > 0 {
+            let q = r0 / r1;
+            let (r, a) = (r0 - q * r1, a0 - q * a1);
+            r0 = r1;
+            r1 = r;
+            a0 = a1;
+            a1 = a;
+        }
+        if r0 != 1 {
+            panic!("{} has no inverse mod {}!", self, M);
+        }
+        Self::new(a0)
+    }
+    pub fn pow(self, n: i64) -> Self {
+        if n < 0 {
+            return self.pow(-n).inv();
+        }
+        let mut x = self;
+        let mut n = n;
+        let mut r = Self::one();
+        while n > 0 {
+            if n & 1 == 1 {
+                r *= x;
+            }
+            x *= x;
+            n >>= 1;
+        }
+        r
+    }
+}
+impl<const M: u32> std::fmt::Display for StaticModInt<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.unwrap())
+    }
+}
+impl<const M: u32> std::fmt::Debug for StaticModInt<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} (mod {})", self.unwrap(), M)
+    }
+}
+agroup! {
+    StaticModInt<M> where [const M: u32];
+    zero = StaticModInt(0);
+    add(self, other) = {
+        let s = self.0 + other.0;
+        StaticModInt(if s >= M { s - M } else { s })
+    };
+    neg(self) = {
+        StaticModInt(if self.0 == 0 { 0 } else { M - self.0 })
+    };
+}
+monoid! {
+    StaticModInt<M> where [const M: u32];
+    one = StaticModInt::new(1);
+    mul(self, other) = {
+        StaticModInt(Self::reduce(self.0 as u64 * other.0 as u64))
+    };
+}
+ring! {
+    StaticModInt<M> where [const M: u32];
+    div(self, other) = { std::ops::Mul::mul(self, other.inv()) };
+}
+impl<const M: u32> Field for StaticModInt<M> {}
+impl<const M: u32> ModIntLike for StaticModInt<M> {
+    fn like(self, x: i128) -> Self {
+        Self::new(x.rem_euclid(M as i128) as i64)
+    }
+    fn residual(self) -> i128 {
+        self.unwrap() as i128
+    }
+    fn modulo(self) -> i128 {
+        M as i128
+    }
+}
+impl<const M: u32> From<i64> for StaticModInt<M> {
+    fn from(x: i64) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<const M: u32> std::ops::Add<i64> for StaticModInt<M> {
+    type Output = Self;
+    fn add(self, other: i64) -> Self {
+        self + Self::new(other)
+    }
+}
+impl<const M: u32> std::ops::Add<StaticModInt<M>> for i64 {
+    type Output = StaticModInt<M>;
+    fn add(self, other: StaticModInt<M>) -> StaticModInt<M> {
+        other + self
+    }
+}
+impl<const M: u32> std::ops::AddAssign<i64> for StaticModInt<M> {
+    fn add_assign(&mut self, other: i64) {
+        *self = *self + other;
+    }
+}
+impl<const M: u32> std::ops::Sub<i64> for StaticModInt<M> {
+    type Output = Self;
+    fn sub(self, other: i64) -> Self {
+        self - Self::new(other)
+    }
+}
+impl<const M: u32> std::ops::Sub<StaticModInt<M>> for i64 {
+    type Output = StaticModInt<M>;
+    fn sub(self, other: StaticModInt<M>) -> StaticModInt<M> {
+        StaticModInt::new(self) - other
+    }
+}
+impl<const M: u32> std::ops::SubAssign<i64> for StaticModInt<M> {
+    fn sub_assign(&mut self, other: i64) {
+        *self = *self - other;
+    }
+}
+impl<const M: u32> std::ops::Mul<i64> for StaticModInt<M> {
+    type Output = Self;
+    fn mul(self, other: i64) -> Self {
+        self * Self::new(other)
+    }
+}
+impl<const M: u32> std::ops::Mul<StaticModInt<M>> for i64 {
+    type Output = StaticModInt<M>;
+    fn mul(self, other: StaticModInt<M>) -> StaticModInt<M> {
+        other * self
+    }
+}
+impl<const M: u32> std::ops::MulAssign<i64> for StaticModInt<M> {
+    fn mul_assign(&mut self, other: i64) {
+        *self = *self * other;
+    }
+}
+impl<const M: u32> std::ops::Div<i64> for StaticModInt<M> {
+    type Output = Self;
+    fn div(self, other: i64) -> Self {
+        self / Self::new(other)
+    }
+}
+impl<const M: u32> std::ops::Div<StaticModInt<M>> for i64 {
+    type Output = StaticModInt<M>;
+    fn div(self, other: StaticModInt<M>) -> StaticModInt<M> {
+        StaticModInt::new(self) / other
+    }
+}
+impl<const M: u32> std::ops::DivAssign<i64> for StaticModInt<M> {
+    fn div_assign(&mut self, other: i64) {
+        *self = *self / other;
+    }
+}
+
+#[cfg(test)]
+mod test_modint_static {
+    use crate::algebra::modint_static::*;
+
+    type Mint = ModInt998244353;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(Mint::new(1) + Mint::new(1), Mint::new(2));
+        assert_eq!(Mint::new(1) + Mint::new(-1), Mint::zero());
+        assert_eq!(Mint::new(1) - 1, Mint::zero());
+        assert_eq!(Mint::new(1) - Mint::new(-3), Mint::new(4));
+        assert_eq!(-Mint::new(1), Mint::new(-1));
+        assert_eq!(Mint::new(-1) * 2, Mint::new(-2));
+        assert_eq!((Mint::new(1) / -3) * 6, Mint::new(-2));
+        assert_eq!(Mint::new(-1).unwrap(), 998_244_352);
+        assert_eq!(Mint::one().unwrap(), 1);
+        assert_eq!(Mint::modulo(), 998_244_353);
+    }
+
+    #[test]
+    fn test_mul_large() {
+        type M7 = ModInt1000000007;
+        let x = M7::new(1_000_000_006);
+        assert_eq!(x * x, M7::one());
+        let a: i64 = 123_456_789;
+        let b: i64 = 987_654_321;
+        assert_eq!(
+            (M7::new(a) * M7::new(b)).unwrap() as i64,
+            a * b % 1_000_000_007
+        );
+        assert_eq!(StaticModInt::<7>::new(3) * 5, StaticModInt::<7>::new(1));
+    }
+
+    #[test]
+    fn test_pow_inv() {
+        assert_eq!(Mint::new(2).pow(10), Mint::new(1024));
+        assert_eq!(Mint::new(-2).pow(11), Mint::new(-2048));
+        assert_eq!(Mint::new(3).pow(-1) * 3, Mint::one());
+        // composite modulo
+        type M9 = StaticModInt<9>;
+        assert_eq!(M9::new(2).inv(), M9::new(5));
+    }
+
+    #[test]
+    fn test_mut() {
+        let mut m = Mint::zero();
+        m -= 1;
+        assert_eq!(m, Mint::new(-1));
+        m *= 2;
+        assert_eq!(m, Mint::new(-2));
+        m += 2;
+        assert_eq!(m, Mint::zero());
+        m += 1;
+        m /= 3;
+        assert_eq!(m + m + m, Mint::one());
+    }
+
+    #[test]
+    fn test_matrix() {
+        use crate::algebra::matrix::*;
+        let fib = Matrix::new(vec![
+            vec![Mint::one(), Mint::one()],
+            vec![Mint::one(), Mint::zero()],
+        ]);
+        assert_eq!(
+            fib.pow(10),
+            Matrix::new(vec![
+                vec![Mint::new(89), Mint::new(55)],
+                vec![Mint::new(55), Mint::new(34)],
+            ])
+        );
+    }
+}
@@ -18,7 +18,7 @@ macro_rules! ring {
         impl<$($params)*> std::ops::DivAssign for $type where Self: Clone {
             fn div_assign(&mut $self, $other: Self) { *$self = (*$self).clone() / $other; }
         }
-        impl Ring for $type {}
+        impl<$($params)*> Ring for $type {}
     };
     (
         $type:ty;
@@ -30,4 +30,5 @@ macro_rules! ring {
 }
 
 impl Ring for i64 {}
+impl Ring for i128 {}
 impl Ring for f64 {}
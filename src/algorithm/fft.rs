@@ -1,72 +1,107 @@
 /// Algorithm - Fast Fourier Transform
 use crate::algebra::complex::*;
-use crate::algebra::monoid::*;
 
 pub struct FFT;
 impl FFT {
-    fn _fft(f: &Vec<Complex<f64>>, dir: f64) -> Vec<Complex<f64>> {
+    /// w[k] = exp(2 pi i k / n) for k < n/2
+    fn twiddles(n: usize) -> Vec<Complex<f64>> {
+        let pi = (-1.0_f64).acos();
+        (0..n / 2)
+            .map(|k| {
+                let theta = 2.0 * pi * (k as f64) / (n as f64);
+                Complex(theta.cos(), theta.sin())
+            })
+            .collect()
+    }
+    /// In-place iterative butterfly (without 1/n scaling);
+    /// `f.len()` should be 2**pow and `w` should be `twiddles(f.len())`
+    fn transform(f: &mut [Complex<f64>], w: &[Complex<f64>], inverse: bool) {
         let n = f.len();
-        if n < 2 {
-            return f.clone();
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j ^= bit;
+            if i < j {
+                f.swap(i, j);
+            }
         }
-        let f0: Vec<Complex<f64>> = (0..n / 2).map(|i| f[i * 2].clone()).collect();
-        let f1: Vec<Complex<f64>> = (0..n / 2).map(|i| f[i * 2 + 1].clone()).collect();
-        let g0 = FFT::_fft(&f0, dir);
-        let g1 = FFT::_fft(&f1, dir);
-        let pi = (-1.0_f64).acos();
-        let theta = 2.0 * pi / (n as f64);
-        let z = Complex(theta.cos(), theta.sin() * dir);
-        let mut ac = Complex::one();
-        let mut g = vec![];
-        for i in 0..n {
-            g.push(g0[i % (n / 2)] + (ac * g1[i % (n / 2)]));
-            ac = z * ac;
+        let mut len = 2;
+        while len <= n {
+            let step = n / len;
+            for start in (0..n).step_by(len) {
+                for k in 0..len / 2 {
+                    let t = w[k * step];
+                    let t = if inverse { Complex(t.0, -t.1) } else { t };
+                    let u = f[start + k];
+                    let v = f[start + k + len / 2] * t;
+                    f[start + k] = u + v;
+                    f[start + k + len / 2] = u - v;
+                }
+            }
+            len <<= 1;
         }
-        g
-    }
-    fn fft(f: &Vec<Complex<f64>>) -> Vec<Complex<f64>> {
-        FFT::_fft(f, 1.0)
-    }
-    fn defft(f: &Vec<Complex<f64>>) -> Vec<Complex<f64>> {
-        FFT::_fft(f, -1.0)
     }
 
-    fn _convolution(x: &Vec<Complex<f64>>, y: &Vec<Complex<f64>>) -> Vec<Complex<f64>> {
-        let m = x.len();
-        let xh = FFT::fft(&x);
-        let yh = FFT::fft(&y);
-        let xyh = (0..m).map(|i| (xh[i] * yh[i]) * (1.0 / m as f64)).collect();
-        FFT::defft(&xyh)
-    }
-
-    pub fn convolution(f: &Vec<f64>, g: &Vec<f64>) -> Vec<f64> {
-        assert!(f[0] == 0.0);
-        assert!(g[0] == 0.0);
-        let n = std::cmp::max(f.len(), g.len());
-        let mut m = 1;
-        while m < n {
-            m <<= 1
+    /// h[k] = sum_{i+j=k} f[i] * g[j]
+    /// Two real sequences are packed into one complex transform (f + ig).
+    /// f[0] and g[0] may be nonzero, and the result has exactly f.len() + g.len() - 1 terms
+    /// (not padded to a power of two); empty for empty inputs.
+    pub fn convolution(f: &[f64], g: &[f64]) -> Vec<f64> {
+        if f.is_empty() || g.is_empty() {
+            return vec![];
         }
-        m <<= 1; // length should be 2**pow
-        let x: Vec<Complex<f64>> = f
-            .iter()
-            .map(|&k| Complex(k, 0.0))
-            .chain((0..m - f.len()).map(|_| Complex(0.0, 0.0)))
+        let size = f.len() + g.len() - 1;
+        let mut n = 1;
+        while n < size {
+            n <<= 1;
+        }
+        let w = FFT::twiddles(n);
+        let mut z: Vec<Complex<f64>> = (0..n)
+            .map(|i| {
+                Complex(
+                    if i < f.len() { f[i] } else { 0.0 },
+                    if i < g.len() { g[i] } else { 0.0 },
+                )
+            })
             .collect();
-        let y: Vec<Complex<f64>> = g
-            .iter()
-            .map(|&k| Complex(k, 0.0))
-            .chain((0..m - g.len()).map(|_| Complex(0.0, 0.0)))
+        FFT::transform(&mut z, &w, false);
+        // F[k] = (Z[k] + conj(Z[-k])) / 2, G[k] = (Z[k] - conj(Z[-k])) / 2i
+        let mut h: Vec<Complex<f64>> = (0..n)
+            .map(|k| {
+                let a = z[k];
+                let b = z[(n - k) % n];
+                let b = Complex(b.0, -b.1);
+                let d = a * a - b * b; // = 4i F[k] G[k]
+                Complex(d.1, -d.0) * (0.25 / n as f64)
+            })
             .collect();
+        FFT::transform(&mut h, &w, true);
+        h.iter().take(size).map(|c| c.0).collect()
+    }
 
-        let z = FFT::_convolution(&x, &y);
-        z.iter().map(|c| c.0).collect()
+    /// Convolution on integers with rounding.
+    /// The result is exact while every sum_i |f[i]| * |g[k-i]| is below about 5e13,
+    /// e.g. f and g of length 2^19 with |f[i]|, |g[j]| <= 1e4 (the result fits in 2^20).
+    pub fn convolution_int(f: &[i64], g: &[i64]) -> Vec<i64> {
+        let f: Vec<f64> = f.iter().map(|&x| x as f64).collect();
+        let g: Vec<f64> = g.iter().map(|&x| x as f64).collect();
+        FFT::convolution(&f, &g)
+            .iter()
+            .map(|&x| x.round() as i64)
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod test_fft {
+    use crate::algebra::modint::*;
     use crate::algorithm::fft::*;
+    use crate::algorithm::ntt::*;
+    use crate::num::random::xorshift::*;
 
     #[test]
     fn it_works() {
@@ -78,4 +113,46 @@ mod test_fft {
             assert!((expected[k] - c[k]).abs() < 1e-6);
         }
     }
+
+    #[test]
+    fn test_int() {
+        assert_eq!(FFT::convolution_int(&[3], &[-5]), vec![-15]);
+        assert_eq!(FFT::convolution_int(&[1, 2, 3], &[]), vec![]);
+        assert_eq!(
+            FFT::convolution_int(&[1, 2, 3, 4], &[5, 6, 7]),
+            vec![5, 16, 34, 52, 45, 28]
+        );
+        let f: Vec<i64> = (0..3000).map(|i| (i * i * 7919) % 20001 - 10000).collect();
+        let g: Vec<i64> = (0..2000).map(|i| (i * 104729) % 20001 - 10000).collect();
+        let mut expected = vec![0; f.len() + g.len() - 1];
+        for (i, &x) in f.iter().enumerate() {
+            for (j, &y) in g.iter().enumerate() {
+                expected[i + j] += x * y;
+            }
+        }
+        assert_eq!(FFT::convolution_int(&f, &g), expected);
+    }
+
+    #[test]
+    fn test_int_large() {
+        // length 2^19 each (the result fits in 2^20) with |x| <= 1e4
+        let mut rand = XorShift::new();
+        let n = 1 << 19;
+        let f: Vec<i64> = (0..n).map(|_| rand.gen::<i64>() % 10001).collect();
+        let g: Vec<i64> = (0..n)
+            .map(|_| if rand.gen::<bool>() { 10000 } else { -10000 })
+            .collect();
+        let h = FFT::convolution_int(&f, &g);
+        assert_eq!(h.len(), 2 * n - 1);
+        // exact check modulo two NTT primes; their product exceeds 2 * max |h[k]|
+        for &p in [MOD_998244353, 167_772_161].iter() {
+            let ntt = NTT::new(p, 3);
+            let fm: Vec<ModInt> = f.iter().map(|&x| ModInt::new(x as i128, p)).collect();
+            let gm: Vec<ModInt> = g.iter().map(|&x| ModInt::new(x as i128, p)).collect();
+            let hm = ntt.convolution(&fm, &gm);
+            for (&x, y) in h.iter().zip(hm.iter()) {
+                assert_eq!((x as i128).rem_euclid(p), y.0);
+            }
+        }
+    }
 }
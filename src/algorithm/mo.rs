@@ -0,0 +1,157 @@
+/// Algorithm - Mo's Algorithm (with Hilbert order)
+pub trait MoState {
+    type Answer;
+    /// [l, r) -> [l - 1, r), where i = l - 1
+    fn add_left(&mut self, i: usize);
+    /// [l, r) -> [l, r + 1), where i = r
+    fn add_right(&mut self, i: usize);
+    /// [l, r) -> [l + 1, r), where i = l
+    fn remove_left(&mut self, i: usize);
+    /// [l, r) -> [l, r - 1), where i = r - 1
+    fn remove_right(&mut self, i: usize);
+    fn answer(&self) -> Self::Answer;
+}
+
+/// Index of (x, y) on the Hilbert curve over [0, 2^k) x [0, 2^k)
+pub fn hilbert_order(x: usize, y: usize, k: u32) -> u64 {
+    let n = 1 << k;
+    let (mut x, mut y) = (x, y);
+    let mut d = 0;
+    let mut s = n >> 1;
+    while s > 0 {
+        let rx = (x & s > 0) as usize;
+        let ry = (y & s > 0) as usize;
+        d += (s as u64) * (s as u64) * ((3 * rx) ^ ry) as u64;
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    d
+}
+
+/// Indices of queries, sorted in the Hilbert order of (start, end)
+pub fn hilbert_sort(queries: &[std::ops::Range<usize>]) -> Vec<usize> {
+    let maxx = queries
+        .iter()
+        .map(|q| q.start.max(q.end))
+        .max()
+        .unwrap_or(0);
+    let k = (usize::BITS - maxx.leading_zeros()).max(1);
+    let mut idx: Vec<usize> = (0..queries.len()).collect();
+    idx.sort_by_cached_key(|&i| hilbert_order(queries[i].start, queries[i].end, k));
+    idx
+}
+
+/// Answers for queries, with O((n + q) sqrt(n)) moves
+pub fn mo<S: MoState>(state: &mut S, queries: &[std::ops::Range<usize>]) -> Vec<S::Answer> {
+    let mut answers: Vec<Option<S::Answer>> = (0..queries.len()).map(|_| None).collect();
+    let (mut l, mut r) = (0, 0);
+    for i in hilbert_sort(queries) {
+        let q = &queries[i];
+        while l > q.start {
+            l -= 1;
+            state.add_left(l);
+        }
+        while r < q.end {
+            state.add_right(r);
+            r += 1;
+        }
+        while l < q.start {
+            state.remove_left(l);
+            l += 1;
+        }
+        while r > q.end {
+            r -= 1;
+            state.remove_right(r);
+        }
+        answers[i] = Some(state.answer());
+    }
+    answers.into_iter().map(|a| a.unwrap()).collect()
+}
+
+#[cfg(test)]
+mod test_mo {
+    use crate::algorithm::mo::*;
+    use crate::num::random::xorshift::*;
+
+    /// Number of distinct values
+    struct Distinct {
+        xs: Vec<usize>,
+        count: Vec<usize>,
+        distinct: usize,
+    }
+    impl Distinct {
+        fn add(&mut self, i: usize) {
+            self.count[self.xs[i]] += 1;
+            if self.count[self.xs[i]] == 1 {
+                self.distinct += 1;
+            }
+        }
+        fn remove(&mut self, i: usize) {
+            self.count[self.xs[i]] -= 1;
+            if self.count[self.xs[i]] == 0 {
+                self.distinct -= 1;
+            }
+        }
+    }
+    impl MoState for Distinct {
+        type Answer = usize;
+        fn add_left(&mut self, i: usize) {
+            self.add(i)
+        }
+        fn add_right(&mut self, i: usize) {
+            self.add(i)
+        }
+        fn remove_left(&mut self, i: usize) {
+            self.remove(i)
+        }
+        fn remove_right(&mut self, i: usize) {
+            self.remove(i)
+        }
+        fn answer(&self) -> usize {
+            self.distinct
+        }
+    }
+
+    #[test]
+    fn test_hilbert_order() {
+        // 1 2
+        // 0 3
+        assert_eq!(hilbert_order(0, 0, 1), 0);
+        assert_eq!(hilbert_order(0, 1, 1), 1);
+        assert_eq!(hilbert_order(1, 1, 1), 2);
+        assert_eq!(hilbert_order(1, 0, 1), 3);
+        let mut ds: Vec<u64> = (0..64).map(|i| hilbert_order(i % 8, i / 8, 3)).collect();
+        ds.sort();
+        assert_eq!(ds, (0..64).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_distinct() {
+        let mut rand = XorShift::new();
+        let n = 200;
+        let xs: Vec<usize> = (0..n).map(|_| rand.gen::<usize>() % 20).collect();
+        let queries: Vec<std::ops::Range<usize>> = (0..300)
+            .map(|_| {
+                let l = rand.gen::<usize>() % (n + 1);
+                let r = rand.gen::<usize>() % (n + 1);
+                l.min(r)..l.max(r)
+            })
+            .collect();
+        let mut state = Distinct {
+            xs: xs.clone(),
+            count: vec![0; 20],
+            distinct: 0,
+        };
+        let answers = mo(&mut state, &queries);
+        for (q, &ans) in queries.iter().zip(answers.iter()) {
+            let set: std::collections::HashSet<usize> = xs[q.clone()].iter().cloned().collect();
+            assert_eq!(ans, set.len());
+        }
+    }
+}
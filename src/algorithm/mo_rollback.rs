@@ -0,0 +1,135 @@
+/// Algorithm - Mo's Algorithm with Rollback (no removals)
+pub trait MoRollbackState {
+    type Answer;
+    /// [l, r) -> [l - 1, r), where i = l - 1
+    fn add_left(&mut self, i: usize);
+    /// [l, r) -> [l, r + 1), where i = r
+    fn add_right(&mut self, i: usize);
+    /// Push the current state as a checkpoint
+    fn snapshot(&mut self);
+    /// Restore the last checkpoint, and pop it
+    fn rollback(&mut self);
+    fn answer(&self) -> Self::Answer;
+}
+
+/// Answers for queries, with O(n sqrt(q) + q sqrt(n)) additions
+pub fn mo_rollback<S: MoRollbackState>(
+    state: &mut S,
+    queries: &[std::ops::Range<usize>],
+) -> Vec<S::Answer> {
+    let n = queries.iter().map(|q| q.end).max().unwrap_or(0);
+    let q = queries.len().max(1);
+    let width = ((n as f64 / (q as f64).sqrt()).ceil() as usize).max(1);
+    let mut idx: Vec<usize> = (0..queries.len()).collect();
+    idx.sort_by_key(|&i| (queries[i].start / width, queries[i].end));
+    let mut answers: Vec<Option<S::Answer>> = (0..queries.len()).map(|_| None).collect();
+    let mut i = 0;
+    while i < idx.len() {
+        let block = queries[idx[i]].start / width;
+        let border = (block + 1) * width;
+        let mut r = border;
+        state.snapshot();
+        while i < idx.len() && queries[idx[i]].start / width == block {
+            let query = &queries[idx[i]];
+            if query.end <= border {
+                // short query, from the scratch
+                state.snapshot();
+                for j in query.clone() {
+                    state.add_right(j);
+                }
+                answers[idx[i]] = Some(state.answer());
+                state.rollback();
+            } else {
+                while r < query.end {
+                    state.add_right(r);
+                    r += 1;
+                }
+                state.snapshot();
+                for j in (query.start..border).rev() {
+                    state.add_left(j);
+                }
+                answers[idx[i]] = Some(state.answer());
+                state.rollback();
+            }
+            i += 1;
+        }
+        state.rollback();
+    }
+    answers.into_iter().map(|a| a.unwrap()).collect()
+}
+
+#[cfg(test)]
+mod test_mo_rollback {
+    use crate::algorithm::mo_rollback::*;
+    use crate::num::random::xorshift::*;
+
+    /// Max frequency of values
+    struct Mode {
+        xs: Vec<usize>,
+        count: Vec<usize>,
+        max: usize,
+        history: Vec<(usize, usize)>, // (value, max before the addition)
+        checkpoints: Vec<usize>,
+    }
+    impl Mode {
+        fn add(&mut self, i: usize) {
+            let x = self.xs[i];
+            self.history.push((x, self.max));
+            self.count[x] += 1;
+            self.max = self.max.max(self.count[x]);
+        }
+    }
+    impl MoRollbackState for Mode {
+        type Answer = usize;
+        fn add_left(&mut self, i: usize) {
+            self.add(i)
+        }
+        fn add_right(&mut self, i: usize) {
+            self.add(i)
+        }
+        fn snapshot(&mut self) {
+            self.checkpoints.push(self.history.len());
+        }
+        fn rollback(&mut self) {
+            let len = self.checkpoints.pop().unwrap();
+            while self.history.len() > len {
+                let (x, max) = self.history.pop().unwrap();
+                self.count[x] -= 1;
+                self.max = max;
+            }
+        }
+        fn answer(&self) -> usize {
+            self.max
+        }
+    }
+
+    #[test]
+    fn test_mode() {
+        let mut rand = XorShift::new();
+        let n = 200;
+        let xs: Vec<usize> = (0..n).map(|_| rand.gen::<usize>() % 20).collect();
+        let queries: Vec<std::ops::Range<usize>> = (0..300)
+            .map(|_| {
+                let l = rand.gen::<usize>() % (n + 1);
+                let r = rand.gen::<usize>() % (n + 1);
+                l.min(r)..l.max(r)
+            })
+            .collect();
+        let mut state = Mode {
+            xs: xs.clone(),
+            count: vec![0; 20],
+            max: 0,
+            history: vec![],
+            checkpoints: vec![],
+        };
+        let answers = mo_rollback(&mut state, &queries);
+        for (q, &ans) in queries.iter().zip(answers.iter()) {
+            let mut count = [0; 20];
+            for &x in xs[q.clone()].iter() {
+                count[x] += 1;
+            }
+            assert_eq!(ans, *count.iter().max().unwrap());
+        }
+        assert!(state.history.is_empty());
+    }
+}
@@ -4,4 +4,7 @@ pub mod binary_search_count_if;
 pub mod dfs;
 pub mod fft;
 pub mod gauss_jordan;
+pub mod mo;
+pub mod mo_rollback;
+pub mod ntt;
 pub mod rho;
@@ -0,0 +1,160 @@
+/// Algorithm - Number Theoretic Transform (NTT)
+use crate::algebra::modint::*;
+use crate::num::chinese_remainder_theorem::*;
+
+pub const PRIMITIVE_ROOT_998244353: i128 = 3;
+
+/// NTT on Z/pZ, where p is a prime s.t. p = c * 2^k + 1
+#[derive(Debug, Clone, Copy)]
+pub struct NTT {
+    modulo: i128,
+    root: i128, // primitive root of modulo
+}
+impl NTT {
+    pub fn new(modulo: i128, root: i128) -> Self {
+        Self { modulo, root }
+    }
+    /// In-place butterfly; `f.len()` should be 2**pow
+    fn transform(&self, f: &mut [ModInt], inverse: bool) {
+        let n = f.len();
+        assert!(n.is_power_of_two());
+        assert!((self.modulo - 1) % n as i128 == 0);
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j ^= bit;
+            if i < j {
+                f.swap(i, j);
+            }
+        }
+        let mut len = 2;
+        while len <= n {
+            let mut w = ModInt(self.root, self.modulo).pow((self.modulo - 1) / len as i128);
+            if inverse {
+                w = w.inv();
+            }
+            for start in (0..n).step_by(len) {
+                let mut wk = ModInt(1, self.modulo);
+                for k in 0..len / 2 {
+                    let u = f[start + k];
+                    let v = f[start + k + len / 2] * wk;
+                    f[start + k] = u + v;
+                    f[start + k + len / 2] = u - v;
+                    wk *= w;
+                }
+            }
+            len <<= 1;
+        }
+        if inverse {
+            let ninv = ModInt(n as i128, self.modulo).inv();
+            for x in f.iter_mut() {
+                *x *= ninv;
+            }
+        }
+    }
+    /// h[k] = sum_{i+j=k} f[i] * g[j] under this modulo
+    pub fn convolution(&self, f: &[ModInt], g: &[ModInt]) -> Vec<ModInt> {
+        if f.is_empty() || g.is_empty() {
+            return vec![];
+        }
+        let size = f.len() + g.len() - 1;
+        let mut n = 1;
+        while n < size {
+            n <<= 1;
+        }
+        let mut x: Vec<ModInt> = f
+            .iter()
+            .map(|&a| ModInt::new(a.0, self.modulo))
+            .chain((f.len()..n).map(|_| ModInt(0, self.modulo)))
+            .collect();
+        let mut y: Vec<ModInt> = g
+            .iter()
+            .map(|&b| ModInt::new(b.0, self.modulo))
+            .chain((g.len()..n).map(|_| ModInt(0, self.modulo)))
+            .collect();
+        self.transform(&mut x, false);
+        self.transform(&mut y, false);
+        for (a, &b) in x.iter_mut().zip(y.iter()) {
+            *a *= b;
+        }
+        self.transform(&mut x, true);
+        x.truncate(size);
+        x
+    }
+}
+
+/// Convolution under MOD_998244353
+pub fn convolve(f: &[ModInt], g: &[ModInt]) -> Vec<ModInt> {
+    NTT::new(MOD_998244353, PRIMITIVE_ROOT_998244353).convolution(f, g)
+}
+
+/// Convolution under any modulo (e.g. MOD_1000000007)
+/// with three NTT-friendly primes and CRT.
+/// The length of the result should be less than 2^24.
+pub fn convolve_any_mod(f: &[ModInt], g: &[ModInt]) -> Vec<ModInt> {
+    if f.is_empty() || g.is_empty() {
+        return vec![];
+    }
+    let modulo = f[0].1;
+    let primes = [(167_772_161, 3), (469_762_049, 3), (754_974_721, 11)];
+    let hs: Vec<Vec<ModInt>> = primes
+        .iter()
+        .map(|&(p, root)| NTT::new(p, root).convolution(f, g))
+        .collect();
+    (0..hs[0].len())
+        .map(|k| {
+            let rm: Vec<(i128, i128)> = hs.iter().map(|h| (h[k].0, h[k].1)).collect();
+            let (r, _) = crt(&rm).unwrap();
+            ModInt::new(r, modulo)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_ntt {
+    use crate::algorithm::ntt::*;
+    use crate::mint;
+
+    fn naive(f: &[ModInt], g: &[ModInt]) -> Vec<ModInt> {
+        let modulo = f[0].1;
+        let mut h = vec![ModInt(0, modulo); f.len() + g.len() - 1];
+        for (i, &a) in f.iter().enumerate() {
+            for (j, &b) in g.iter().enumerate() {
+                h[i + j] += a * b;
+            }
+        }
+        h
+    }
+
+    #[test]
+    fn test_convolve() {
+        let f = vec![mint!(1), mint!(2), mint!(3), mint!(4)];
+        let g = vec![mint!(5), mint!(6), mint!(7)];
+        let h: Vec<i128> = convolve(&f, &g).iter().map(|x| x.unwrap()).collect();
+        assert_eq!(h, vec![5, 16, 34, 52, 45, 28]);
+        assert_eq!(convolve(&f, &[]), vec![]);
+    }
+
+    #[test]
+    fn test_convolve_large_values() {
+        let f: Vec<ModInt> = (0..100).map(|i| mint!(MOD_998244353 - 1 - i * i)).collect();
+        let g: Vec<ModInt> = (0..37).map(|i| mint!(i * 12345 + 678)).collect();
+        assert_eq!(convolve(&f, &g), naive(&f, &g));
+    }
+
+    #[test]
+    fn test_convolve_any_mod() {
+        const MOD: i128 = MOD_1000000007;
+        let f: Vec<ModInt> = (0..60).map(|i| ModInt::new(MOD - 1 - i * 7, MOD)).collect();
+        let g: Vec<ModInt> = (0..45).map(|i| ModInt::new(MOD - 2 - i, MOD)).collect();
+        assert_eq!(convolve_any_mod(&f, &g), naive(&f, &g));
+        assert_eq!(
+            convolve_any_mod(&[ModInt(3, MOD)], &[ModInt(5, MOD)]),
+            vec![ModInt(15, MOD)]
+        );
+    }
+}
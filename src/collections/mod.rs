@@ -2,3 +2,4 @@ pub mod btreemultiset;
 pub mod defaultdict;
 pub mod list_macro;
 pub mod multiset;
+pub mod orderedmultiset;
@@ -0,0 +1,315 @@
+/// collections - Ordered MultiSet (Treap with order statistics)
+use crate::num::random::xorshift::*;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    key: T,
+    count: usize, // of the key
+    total: usize, // of the subtree
+    priority: u64,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// BTreeMultiSet with O(log n) rank/nth queries
+#[derive(Debug, Clone)]
+pub struct OrderedMultiSet<T> {
+    root: Link<T>,
+    rand: XorShift,
+}
+impl<T: Sized + Ord> OrderedMultiSet<T> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            rand: XorShift::new(),
+        }
+    }
+    pub fn insert(&mut self, item: T) {
+        let (less, rest) = split(self.root.take(), &|key| key < &item);
+        let (equal, greater) = split(rest, &|key| key <= &item);
+        let equal = match equal {
+            Some(mut node) => {
+                node.count += 1;
+                node.total += 1;
+                Some(node)
+            }
+            None => Some(Box::new(Node {
+                key: item,
+                count: 1,
+                total: 1,
+                priority: self.rand.gen(),
+                left: None,
+                right: None,
+            })),
+        };
+        self.root = merge(merge(less, equal), greater);
+    }
+    /// Count of the item
+    pub fn get(&self, item: &T) -> Option<usize> {
+        let mut t = &self.root;
+        while let Some(node) = t {
+            t = match item.cmp(&node.key) {
+                std::cmp::Ordering::Less => &node.left,
+                std::cmp::Ordering::Greater => &node.right,
+                std::cmp::Ordering::Equal => return Some(node.count),
+            };
+        }
+        None
+    }
+    /// Remove one of the item (if exists)
+    pub fn remove(&mut self, item: T) {
+        let (less, rest) = split(self.root.take(), &|key| key < &item);
+        let (equal, greater) = split(rest, &|key| key <= &item);
+        let equal = match equal {
+            Some(mut node) if node.count > 1 => {
+                node.count -= 1;
+                node.total -= 1;
+                Some(node)
+            }
+            _ => None,
+        };
+        self.root = merge(merge(less, equal), greater);
+    }
+    pub fn len(&self) -> usize {
+        total(&self.root)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+    /// Number of items < x
+    pub fn rank(&self, x: &T) -> usize {
+        self.count_while(|key| key < x)
+    }
+    /// Number of items satisfying pred, which is monotone (true, .., true, false, .., false)
+    fn count_while<F: Fn(&T) -> bool>(&self, pred: F) -> usize {
+        let mut t = &self.root;
+        let mut count = 0;
+        while let Some(node) = t {
+            if pred(&node.key) {
+                count += total(&node.left) + node.count;
+                t = &node.right;
+            } else {
+                t = &node.left;
+            }
+        }
+        count
+    }
+    /// k-th (0-indexed) smallest item
+    pub fn nth(&self, k: usize) -> Option<&T> {
+        let mut t = &self.root;
+        let mut k = k;
+        while let Some(node) = t {
+            let left = total(&node.left);
+            if k < left {
+                t = &node.left;
+            } else if k < left + node.count {
+                return Some(&node.key);
+            } else {
+                k -= left + node.count;
+                t = &node.right;
+            }
+        }
+        None
+    }
+    /// Number of items in range
+    pub fn count_range<R: std::ops::RangeBounds<T>>(&self, range: R) -> usize {
+        use std::ops::Bound::*;
+        let below_start = match range.start_bound() {
+            Included(a) => self.count_while(|key| key < a),
+            Excluded(a) => self.count_while(|key| key <= a),
+            Unbounded => 0,
+        };
+        let until_end = match range.end_bound() {
+            Included(b) => self.count_while(|key| key <= b),
+            Excluded(b) => self.count_while(|key| key < b),
+            Unbounded => self.len(),
+        };
+        until_end.saturating_sub(below_start)
+    }
+    /// Min item >= x
+    pub fn lower_bound(&self, x: &T) -> Option<&T> {
+        self.nth(self.rank(x))
+    }
+    /// Min item > x
+    pub fn upper_bound(&self, x: &T) -> Option<&T> {
+        self.nth(self.count_while(|key| key <= x))
+    }
+    /// (item, count) in range, in ascending order
+    pub fn range<R: std::ops::RangeBounds<T>>(&self, range: R) -> std::vec::IntoIter<(&T, &usize)> {
+        let mut items = vec![];
+        collect(&self.root, &range, &mut items);
+        items.into_iter()
+    }
+    pub fn min<R: std::ops::RangeBounds<T>>(&self, range: R) -> Option<&T> {
+        use std::ops::Bound::*;
+        let k = match range.start_bound() {
+            Included(a) => self.count_while(|key| key < a),
+            Excluded(a) => self.count_while(|key| key <= a),
+            Unbounded => 0,
+        };
+        self.nth(k).filter(|x| range.contains(x))
+    }
+    pub fn max<R: std::ops::RangeBounds<T>>(&self, range: R) -> Option<&T> {
+        use std::ops::Bound::*;
+        let k = match range.end_bound() {
+            Included(b) => self.count_while(|key| key <= b),
+            Excluded(b) => self.count_while(|key| key < b),
+            Unbounded => self.len(),
+        };
+        if k == 0 {
+            return None;
+        }
+        self.nth(k - 1).filter(|x| range.contains(x))
+    }
+}
+impl<T: Sized + Ord> Default for OrderedMultiSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn total<T>(t: &Link<T>) -> usize {
+    t.as_ref().map_or(0, |node| node.total)
+}
+fn pull<T>(node: &mut Node<T>) {
+    node.total = total(&node.left) + node.count + total(&node.right);
+}
+/// (keys satisfying pred, the rest), where pred is monotone
+fn split<T, F: Fn(&T) -> bool>(t: Link<T>, pred: &F) -> (Link<T>, Link<T>) {
+    match t {
+        None => (None, None),
+        Some(mut node) => {
+            if pred(&node.key) {
+                let (a, b) = split(node.right.take(), pred);
+                node.right = a;
+                pull(&mut node);
+                (Some(node), b)
+            } else {
+                let (a, b) = split(node.left.take(), pred);
+                node.left = b;
+                pull(&mut node);
+                (a, Some(node))
+            }
+        }
+    }
+}
+fn merge<T>(a: Link<T>, b: Link<T>) -> Link<T> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority > b.priority {
+                a.right = merge(a.right.take(), Some(b));
+                pull(&mut a);
+                Some(a)
+            } else {
+                b.left = merge(Some(a), b.left.take());
+                pull(&mut b);
+                Some(b)
+            }
+        }
+    }
+}
+fn collect<'a, T: Ord, R: std::ops::RangeBounds<T>>(
+    t: &'a Link<T>,
+    range: &R,
+    items: &mut Vec<(&'a T, &'a usize)>,
+) {
+    use std::ops::Bound::*;
+    if let Some(node) = t {
+        let above_start = match range.start_bound() {
+            Included(a) => a <= &node.key,
+            Excluded(a) => a < &node.key,
+            Unbounded => true,
+        };
+        let below_end = match range.end_bound() {
+            Included(b) => &node.key <= b,
+            Excluded(b) => &node.key < b,
+            Unbounded => true,
+        };
+        if above_start {
+            collect(&node.left, range, items);
+        }
+        if above_start && below_end {
+            items.push((&node.key, &node.count));
+        }
+        if below_end {
+            collect(&node.right, range, items);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_ordered_multiset {
+    use crate::collections::btreemultiset::*;
+    use crate::collections::orderedmultiset::*;
+
+    #[test]
+    fn test_multiset() {
+        let mut c = OrderedMultiSet::new();
+        assert_eq!(None, c.get(&"hoge"));
+        c.insert("hoge");
+        c.insert("fuga");
+        c.insert("hoge");
+        assert_eq!(Some(2), c.get(&"hoge"));
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.nth(0), Some(&"fuga"));
+        assert_eq!(c.nth(2), Some(&"hoge"));
+        assert_eq!(c.nth(3), None);
+        c.remove("hoge");
+        c.remove("hoge");
+        c.remove("hoge");
+        assert_eq!(None, c.get(&"hoge"));
+        assert_eq!(c.len(), 1);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        let mut s = OrderedMultiSet::new();
+        let mut b = BTreeMultiSet::new();
+        let mut xs: Vec<i64> = vec![];
+        for _ in 0..1000 {
+            let x = rand.gen::<i64>() % 30;
+            if rand.gen::<usize>() % 3 == 2 {
+                s.remove(x);
+                b.remove(x);
+                if let Some(i) = xs.iter().position(|&y| y == x) {
+                    xs.remove(i);
+                }
+            } else {
+                s.insert(x);
+                b.insert(x);
+                xs.push(x);
+            }
+            xs.sort();
+            assert_eq!(s.len(), xs.len());
+            assert_eq!(s.get(&x), b.get(&x));
+            let y = rand.gen::<i64>() % 30;
+            assert_eq!(s.rank(&y), xs.iter().filter(|&&z| z < y).count());
+            let k = rand.gen::<usize>() % (xs.len() + 1);
+            assert_eq!(s.nth(k), xs.get(k));
+            assert_eq!(s.lower_bound(&y), xs.iter().find(|&&z| z >= y));
+            assert_eq!(s.upper_bound(&y), xs.iter().find(|&&z| z > y));
+            let (lo, hi) = (std::cmp::min(x, y), std::cmp::max(x, y));
+            assert_eq!(
+                s.count_range(lo..hi),
+                xs.iter().filter(|&&z| lo <= z && z < hi).count()
+            );
+            assert_eq!(
+                s.count_range(lo..=hi),
+                xs.iter().filter(|&&z| lo <= z && z <= hi).count()
+            );
+            assert_eq!(s.count_range(hi..lo), 0);
+            assert_eq!(
+                s.range(lo..hi).collect::<Vec<_>>(),
+                b.range(lo..hi).collect::<Vec<_>>()
+            );
+            assert_eq!(s.min(lo..=hi), b.min(lo..=hi));
+            assert_eq!(s.max(lo..hi), b.max(lo..hi));
+            assert_eq!(s.max(..), b.max(..));
+        }
+    }
+}
@@ -1,79 +1,164 @@
 /// Graph - Directed - Dinic's MaxFlow - O(V^2 E)
 use crate::algebra::group_additive::*;
 use crate::algebra::hyper::*;
+use crate::graph::directed::flow_edge::*;
 
+#[derive(Debug, Clone)]
+struct Edge<X> {
+    to: usize,
+    rev: usize,    // index of the reverse edge in g[to]
+    cap: Hyper<X>, // residual
+}
+
+#[derive(Debug, Clone)]
 pub struct Dinic<X> {
     size: usize,
     s: usize,
     t: usize,
-    g: Vec<Vec<(usize, Hyper<X>)>>,
+    g: Vec<Vec<Edge<X>>>,
+    pos: Vec<(usize, usize)>, // edge id -> (from, index in g[from])
+    flow: Hyper<X>,
 }
 impl<X: std::fmt::Debug + Copy + Eq + Ord + AGroup> Dinic<X> {
+    /// Edge ids are numbered in the order of neigh[0], neigh[1], ..
     pub fn new(s: usize, t: usize, neigh: &[Vec<(usize, Hyper<X>)>]) -> Self {
         let size = neigh.len();
-        let mut g = vec![vec![]; size];
-        for u in 0..size {
-            for &(v, cap) in neigh[u].iter() {
-                g[u].push((v, cap));
-                g[v].push((u, Hyper::zero()));
+        let mut dinic = Self {
+            size,
+            s,
+            t,
+            g: vec![vec![]; size],
+            pos: vec![],
+            flow: Hyper::zero(),
+        };
+        for (u, es) in neigh.iter().enumerate() {
+            for &(v, cap) in es.iter() {
+                dinic.add_edge(u, v, cap);
             }
         }
-        Self { size, s, t, g }
+        dinic
+    }
+    /// Add an edge u -> v, and returns its id
+    pub fn add_edge(&mut self, u: usize, v: usize, cap: Hyper<X>) -> usize {
+        let id = self.pos.len();
+        let (i, j) = (
+            self.g[u].len(),
+            self.g[v].len() + if u == v { 1 } else { 0 },
+        );
+        self.pos.push((u, i));
+        self.g[u].push(Edge { to: v, rev: j, cap });
+        self.g[v].push(Edge {
+            to: u,
+            rev: i,
+            cap: Hyper::zero(),
+        });
+        id
+    }
+    pub fn edge(&self, id: usize) -> FlowEdge<X> {
+        let (u, i) = self.pos[id];
+        let e = &self.g[u][i];
+        let r = &self.g[e.to][e.rev];
+        FlowEdge {
+            from: u,
+            to: e.to,
+            cap: e.cap + r.cap,
+            flow: r.cap,
+        }
     }
-    pub fn maxflow(&self) -> Hyper<X> {
-        let mut sum = Hyper::zero();
-        let mut flw = vec![vec![Hyper::zero(); self.size]; self.size];
-        loop {
-            let level = self.levelize(&flw);
+    pub fn edges(&self) -> Vec<FlowEdge<X>> {
+        (0..self.pos.len()).map(|id| self.edge(id)).collect()
+    }
+    /// Max flow value; flows so far are kept, so this can be re-run after adding edges
+    pub fn maxflow(&mut self) -> Hyper<X> {
+        while self.flow < Hyper::Inf {
+            let level = self.levelize();
             if level[self.t] >= self.size {
                 break;
             }
-            sum += self.augment(Hyper::Inf, &mut flw, &level, self.s);
+            let mut iter = vec![0; self.size];
+            loop {
+                let f = self.augment(Hyper::Inf, &level, &mut iter, self.s);
+                if f <= Hyper::zero() {
+                    break;
+                }
+                self.flow += f;
+                if f == Hyper::Inf {
+                    break;
+                }
+            }
         }
-        sum
+        self.flow
     }
-    fn levelize(&self, flw: &[Vec<Hyper<X>>]) -> Vec<usize> {
-        use std::{cmp::Reverse, collections::BinaryHeap};
+    fn levelize(&self) -> Vec<usize> {
         let mut level = vec![self.size; self.size];
-        let mut q = BinaryHeap::new();
-        q.push((Reverse(0), self.s));
+        let mut q = std::collections::VecDeque::new();
         level[self.s] = 0;
-        while let Some((_, u)) = q.pop() {
+        q.push_back(self.s);
+        while let Some(u) = q.pop_front() {
             if u == self.t {
                 break;
             }
-            for &(v, cap) in self.g[u].iter() {
-                if level[v] == self.size && cap > flw[u][v] {
-                    level[v] = level[u] + 1;
-                    q.push((Reverse(level[v]), v));
+            for e in self.g[u].iter() {
+                if level[e.to] == self.size && e.cap > Hyper::zero() {
+                    level[e.to] = level[u] + 1;
+                    q.push_back(e.to);
                 }
             }
         }
         level
     }
     fn augment(
-        &self,
+        &mut self,
         limit: Hyper<X>,
-        mut flw: &mut Vec<Vec<Hyper<X>>>,
         level: &[usize],
+        iter: &mut [usize],
         u: usize,
     ) -> Hyper<X> {
         if u == self.t {
             return limit;
         }
-        for &(v, cap) in self.g[u].iter() {
-            if level[v] > level[u] {
-                let limit = std::cmp::min(limit, cap - flw[u][v]);
-                let f = self.augment(limit, &mut flw, &level, v);
+        while iter[u] < self.g[u].len() {
+            let Edge { to: v, rev, cap } = self.g[u][iter[u]];
+            if level[v] > level[u] && cap > Hyper::zero() {
+                let f = self.augment(std::cmp::min(limit, cap), level, iter, v);
                 if f > Hyper::zero() {
-                    flw[u][v] += f;
-                    flw[v][u] -= f;
+                    let i = iter[u];
+                    self.g[u][i].cap -= f;
+                    self.g[v][rev].cap += f;
                     return f;
                 }
             }
+            iter[u] += 1;
         }
         Hyper::zero()
     }
+    /// Residual graph; (v, residual capacity > 0) for each u
+    pub fn residual(&self) -> Vec<Vec<(usize, Hyper<X>)>> {
+        self.g
+            .iter()
+            .map(|es| {
+                es.iter()
+                    .filter(|e| e.cap > Hyper::zero())
+                    .map(|e| (e.to, e.cap))
+                    .collect()
+            })
+            .collect()
+    }
+    /// The s-side of a minimum cut (after maxflow); vertices reachable from s in the residual graph
+    pub fn min_cut(&self) -> Vec<bool> {
+        let mut visited = vec![false; self.size];
+        let mut stack = vec![self.s];
+        visited[self.s] = true;
+        while let Some(u) = stack.pop() {
+            for e in self.g[u].iter() {
+                if !visited[e.to] && e.cap > Hyper::zero() {
+                    visited[e.to] = true;
+                    stack.push(e.to);
+                }
+            }
+        }
+        visited
+    }
 }
 
 #[cfg(test)]
@@ -107,4 +192,33 @@ mod test_dinic {
         ];
         assert_eq!(Dinic::new(0, 5, &neigh).maxflow(), Real(5));
     }
+
+    #[test]
+    fn test_flows_and_cut() {
+        let mut dinic = Dinic::new(0, 3, &vec![vec![]; 4]);
+        let e0 = dinic.add_edge(0, 1, Real(2_i64));
+        let e1 = dinic.add_edge(0, 2, Real(1));
+        let e2 = dinic.add_edge(1, 3, Real(1));
+        let e3 = dinic.add_edge(2, 3, Real(3));
+        assert_eq!(dinic.maxflow(), Real(2));
+        assert_eq!(
+            dinic.edge(e0),
+            FlowEdge {
+                from: 0,
+                to: 1,
+                cap: Real(2),
+                flow: Real(1)
+            }
+        );
+        assert_eq!(dinic.edge(e1).flow, Real(1));
+        assert_eq!(dinic.edge(e2).flow, Real(1));
+        assert_eq!(dinic.edge(e3).flow, Real(1));
+        assert_eq!(dinic.min_cut(), vec![true, true, false, false]);
+        assert_eq!(dinic.residual()[0], vec![(1, Real(1))]);
+        // incremental
+        let e4 = dinic.add_edge(1, 2, Real(5));
+        assert_eq!(dinic.maxflow(), Real(3));
+        assert_eq!(dinic.edge(e4).flow, Real(1));
+        assert_eq!(dinic.min_cut(), vec![true, false, false, false]);
+    }
 }
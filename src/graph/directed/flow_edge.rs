@@ -0,0 +1,10 @@
+/// Graph - Directed - Edge of Flow Network (result of MaxFlow)
+use crate::algebra::hyper::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlowEdge<X> {
+    pub from: usize,
+    pub to: usize,
+    pub cap: Hyper<X>,
+    pub flow: Hyper<X>,
+}
@@ -1,66 +1,134 @@
 /// Graph - Directed - Ford-Fullkerson's MaxFlow - O(FE)
 use crate::algebra::group_additive::*;
 use crate::algebra::hyper::*;
+use crate::graph::directed::flow_edge::*;
 
+#[derive(Debug, Clone)]
+struct Edge<X> {
+    to: usize,
+    rev: usize,    // index of the reverse edge in g[to]
+    cap: Hyper<X>, // residual
+}
+
+#[derive(Debug, Clone)]
 pub struct FordFulkerson<X> {
     size: usize,
     s: usize,
     t: usize,
-    g: Vec<Vec<(usize, Hyper<X>)>>,
+    g: Vec<Vec<Edge<X>>>,
+    pos: Vec<(usize, usize)>, // edge id -> (from, index in g[from])
+    flow: Hyper<X>,
 }
 
 impl<X: std::fmt::Debug + Copy + Eq + Ord + AGroup> FordFulkerson<X> {
+    /// Edge ids are numbered in the order of neigh[0], neigh[1], ..
     pub fn new(s: usize, t: usize, neigh: &[Vec<(usize, Hyper<X>)>]) -> Self {
         let size = neigh.len();
-        let mut g = vec![vec![]; size];
-        for u in 0..size {
-            for &(v, cap) in neigh[u].iter() {
-                g[u].push((v, cap));
-                g[v].push((u, Hyper::zero()));
+        let mut ff = Self {
+            size,
+            s,
+            t,
+            g: vec![vec![]; size],
+            pos: vec![],
+            flow: Hyper::zero(),
+        };
+        for (u, es) in neigh.iter().enumerate() {
+            for &(v, cap) in es.iter() {
+                ff.add_edge(u, v, cap);
             }
         }
-        Self { size, s, t, g }
+        ff
+    }
+    /// Add an edge u -> v, and returns its id
+    pub fn add_edge(&mut self, u: usize, v: usize, cap: Hyper<X>) -> usize {
+        let id = self.pos.len();
+        let (i, j) = (
+            self.g[u].len(),
+            self.g[v].len() + if u == v { 1 } else { 0 },
+        );
+        self.pos.push((u, i));
+        self.g[u].push(Edge { to: v, rev: j, cap });
+        self.g[v].push(Edge {
+            to: u,
+            rev: i,
+            cap: Hyper::zero(),
+        });
+        id
+    }
+    pub fn edge(&self, id: usize) -> FlowEdge<X> {
+        let (u, i) = self.pos[id];
+        let e = &self.g[u][i];
+        let r = &self.g[e.to][e.rev];
+        FlowEdge {
+            from: u,
+            to: e.to,
+            cap: e.cap + r.cap,
+            flow: r.cap,
+        }
     }
-    pub fn maxflow(&self) -> Hyper<X> {
-        let mut sum = Hyper::zero();
-        let mut flw = vec![vec![Hyper::zero(); self.size]; self.size];
-        loop {
+    pub fn edges(&self) -> Vec<FlowEdge<X>> {
+        (0..self.pos.len()).map(|id| self.edge(id)).collect()
+    }
+    /// Max flow value; flows so far are kept, so this can be re-run after adding edges
+    pub fn maxflow(&mut self) -> Hyper<X> {
+        while self.flow < Hyper::Inf {
             let mut visited = vec![false; self.size];
-            let df = self.augment(Hyper::Inf, &mut flw, &mut visited, self.s);
+            let df = self.augment(Hyper::Inf, &mut visited, self.s);
             if df <= Hyper::zero() {
                 break;
             }
-            sum += df;
+            self.flow += df;
         }
-        sum
+        self.flow
     }
-    fn augment(
-        &self,
-        limit: Hyper<X>,
-        mut flw: &mut [Vec<Hyper<X>>],
-        mut visited: &mut [bool],
-        u: usize,
-    ) -> Hyper<X> {
+    fn augment(&mut self, limit: Hyper<X>, visited: &mut [bool], u: usize) -> Hyper<X> {
         if u == self.t {
             return limit;
         }
         visited[u] = true;
-        for &(v, cap) in self.g[u].iter() {
+        for i in 0..self.g[u].len() {
+            let Edge { to: v, rev, cap } = self.g[u][i];
             if visited[v] {
                 continue;
             }
-            if cap > flw[u][v] {
-                let limit = std::cmp::min(limit, cap - flw[u][v]);
-                let f = self.augment(limit, &mut flw, &mut visited, v);
+            if cap > Hyper::zero() {
+                let f = self.augment(std::cmp::min(limit, cap), visited, v);
                 if f > Hyper::zero() {
-                    flw[u][v] += f;
-                    flw[v][u] -= f;
+                    self.g[u][i].cap -= f;
+                    self.g[v][rev].cap += f;
                     return f;
                 }
             }
         }
         Hyper::zero()
     }
+    /// Residual graph; (v, residual capacity > 0) for each u
+    pub fn residual(&self) -> Vec<Vec<(usize, Hyper<X>)>> {
+        self.g
+            .iter()
+            .map(|es| {
+                es.iter()
+                    .filter(|e| e.cap > Hyper::zero())
+                    .map(|e| (e.to, e.cap))
+                    .collect()
+            })
+            .collect()
+    }
+    /// The s-side of a minimum cut (after maxflow); vertices reachable from s in the residual graph
+    pub fn min_cut(&self) -> Vec<bool> {
+        let mut visited = vec![false; self.size];
+        let mut stack = vec![self.s];
+        visited[self.s] = true;
+        while let Some(u) = stack.pop() {
+            for e in self.g[u].iter() {
+                if !visited[e.to] && e.cap > Hyper::zero() {
+                    visited[e.to] = true;
+                    stack.push(e.to);
+                }
+            }
+        }
+        visited
+    }
 }
 
 #[cfg(test)]
@@ -94,4 +162,24 @@ mod test_ffa {
         ];
         assert_eq!(FordFulkerson::new(0, 5, &neigh).maxflow(), Real(5));
     }
+
+    #[test]
+    fn test_flows_and_cut() {
+        let neigh = vec![
+            vec![(1, Real(2_i64)), (2, Real(1))],
+            vec![(3, Real(1))],
+            vec![(3, Real(3))],
+            vec![],
+        ];
+        let mut ff = FordFulkerson::new(0, 3, &neigh);
+        assert_eq!(ff.maxflow(), Real(2));
+        let flows: Vec<Hyper<i64>> = ff.edges().iter().map(|e| e.flow).collect();
+        assert_eq!(flows, vec![Real(1), Real(1), Real(1), Real(1)]);
+        assert_eq!(ff.min_cut(), vec![true, true, false, false]);
+        let e = ff.add_edge(1, 2, Inf);
+        assert_eq!(ff.maxflow(), Real(3));
+        assert_eq!(ff.edge(e).cap, Inf);
+        assert_eq!(ff.edge(e).flow, Real(1));
+        assert_eq!(ff.min_cut(), vec![true, false, false, false]);
+    }
 }
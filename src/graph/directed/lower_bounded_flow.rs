@@ -0,0 +1,263 @@
+/// Graph - Directed - Flow with Lower Bounds (Circulation) over Dinic
+use crate::algebra::group_additive::*;
+use crate::algebra::hyper::*;
+use crate::graph::directed::dinic::*;
+
+#[derive(Debug, Clone)]
+pub struct LowerBoundedFlow<X> {
+    size: usize,
+    edges: Vec<(usize, usize, X, Hyper<X>)>, // (u, v, lo, hi)
+    demand: Vec<X>,                          // net inflow required at each vertex
+}
+impl<X: std::fmt::Debug + Copy + Eq + Ord + AGroup> LowerBoundedFlow<X> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            edges: vec![],
+            demand: vec![X::zero(); size],
+        }
+    }
+    /// Add an edge u -> v whose flow is in [lo, hi], and returns its id
+    pub fn add_edge(&mut self, u: usize, v: usize, lo: X, hi: Hyper<X>) -> usize {
+        assert!(Hyper::Real(lo) <= hi);
+        self.edges.push((u, v, lo, hi));
+        self.edges.len() - 1
+    }
+    /// Vertex v consumes d (inflow - outflow = d); negative d is a supply.
+    /// The total of the demands should be zero, otherwise nothing is feasible
+    pub fn add_demand(&mut self, v: usize, d: X) {
+        self.demand[v] = self.demand[v] + d;
+    }
+    /// Dinic between the super source/sink, with the id of each edge;
+    /// the flow x on an edge is reduced to x - lo in [0, hi - lo].
+    /// None if the demands are not balanced
+    fn reduce(&self) -> Option<(Dinic<X>, Vec<usize>, Hyper<X>)> {
+        let (ss, tt) = (self.size, self.size + 1);
+        let mut dinic = Dinic::new(ss, tt, &vec![vec![]; self.size + 2]);
+        let mut excess = self.demand.iter().map(|&d| -d).collect::<Vec<X>>();
+        let ids = self
+            .edges
+            .iter()
+            .map(|&(u, v, lo, hi)| {
+                excess[u] = excess[u] - lo;
+                excess[v] = excess[v] + lo;
+                dinic.add_edge(u, v, hi - Hyper::Real(lo))
+            })
+            .collect();
+        let mut required = X::zero();
+        let mut consumed = X::zero();
+        for (v, &x) in excess.iter().enumerate() {
+            if x > X::zero() {
+                dinic.add_edge(ss, v, Hyper::Real(x));
+                required = required + x;
+            } else if x < X::zero() {
+                dinic.add_edge(v, tt, Hyper::Real(-x));
+                consumed = consumed - x;
+            }
+        }
+        if required != consumed {
+            return None;
+        }
+        Some((dinic, ids, Hyper::Real(required)))
+    }
+    /// A feasible flow on each edge; s and t are joined both ways if given
+    fn feasible(&self, st: Option<(usize, usize)>) -> Option<Vec<X>> {
+        let (mut dinic, ids, required) = self.reduce()?;
+        if let Some((s, t)) = st {
+            dinic.add_edge(s, t, Hyper::Inf);
+            dinic.add_edge(t, s, Hyper::Inf);
+        }
+        if dinic.maxflow() < required {
+            return None;
+        }
+        Some(
+            ids.iter()
+                .zip(self.edges.iter())
+                .map(|(&id, &(_, _, lo, _))| dinic.edge(id).flow.unwrap() + lo)
+                .collect(),
+        )
+    }
+    /// A feasible flow on each edge (always finite), satisfying the bounds and the demands
+    pub fn circulation(&self) -> Option<Vec<X>> {
+        self.feasible(None)
+    }
+    /// (max flow s -> t, flow on each edge), or None if infeasible;
+    /// the flow value is the net outflow from s, which may be negative or unbounded.
+    /// s and t should have no demands
+    pub fn max_flow(&self, s: usize, t: usize) -> Option<(Hyper<X>, Vec<Hyper<X>>)> {
+        assert!(s != t);
+        assert!(self.demand[s] == X::zero() && self.demand[t] == X::zero());
+        let flows = self.feasible(Some((s, t)))?;
+        Some(self.with_value(s, self.augment(s, t, &flows)))
+    }
+    /// (min flow s -> t, flow on each edge), or None if infeasible
+    pub fn min_flow(&self, s: usize, t: usize) -> Option<(Hyper<X>, Vec<Hyper<X>>)> {
+        assert!(s != t);
+        assert!(self.demand[s] == X::zero() && self.demand[t] == X::zero());
+        let flows = self.feasible(Some((s, t)))?;
+        Some(self.with_value(s, self.augment(t, s, &flows)))
+    }
+    /// Push flow from -> to as much as possible on the residual graph of the feasible flows
+    fn augment(&self, from: usize, to: usize, flows: &[X]) -> Vec<Hyper<X>> {
+        let mut dinic = Dinic::new(from, to, &vec![vec![]; self.size]);
+        let ids: Vec<(usize, usize)> = self
+            .edges
+            .iter()
+            .zip(flows.iter())
+            .map(|(&(u, v, lo, hi), &f)| {
+                (
+                    dinic.add_edge(u, v, hi - Hyper::Real(f)),
+                    dinic.add_edge(v, u, Hyper::Real(f - lo)),
+                )
+            })
+            .collect();
+        dinic.maxflow();
+        ids.iter()
+            .zip(flows.iter())
+            .map(|(&(fwd, bwd), &f)| dinic.edge(fwd).flow + f - dinic.edge(bwd).flow)
+            .collect()
+    }
+    /// Attach the net outflow from s
+    fn with_value(&self, s: usize, flows: Vec<Hyper<X>>) -> (Hyper<X>, Vec<Hyper<X>>) {
+        let mut value = Hyper::zero();
+        for (&(u, v, _, _), &f) in self.edges.iter().zip(flows.iter()) {
+            if u == s {
+                value += f;
+            }
+            if v == s {
+                value -= f;
+            }
+        }
+        (value, flows)
+    }
+}
+
+#[cfg(test)]
+mod test_lower_bounded_flow {
+    use crate::algebra::hyper::Hyper::*;
+    use crate::graph::directed::lower_bounded_flow::*;
+    use crate::num::random::xorshift::*;
+
+    #[test]
+    fn test_circulation() {
+        let mut lbf = LowerBoundedFlow::new(2);
+        lbf.add_edge(0, 1, 2_i64, Real(3));
+        lbf.add_edge(1, 0, 0, Real(1));
+        assert_eq!(lbf.circulation(), None);
+        let mut lbf = LowerBoundedFlow::new(2);
+        lbf.add_edge(0, 1, 2_i64, Real(3));
+        lbf.add_edge(1, 0, 1, Inf);
+        let flows = lbf.circulation().unwrap();
+        assert!(2 <= flows[0] && flows[0] <= 3);
+        assert_eq!(flows[0], flows[1]);
+    }
+
+    #[test]
+    fn test_max_min() {
+        let mut lbf = LowerBoundedFlow::new(4);
+        lbf.add_edge(0, 1, 1_i64, Real(3));
+        lbf.add_edge(0, 2, 0, Real(2));
+        lbf.add_edge(1, 3, 2, Real(2));
+        lbf.add_edge(2, 3, 0, Real(4));
+        lbf.add_edge(1, 2, 0, Real(1));
+        let (value, flows) = lbf.max_flow(0, 3).unwrap();
+        assert_eq!(value, Real(5));
+        assert_eq!(flows[0] + flows[1], value);
+        let (value, flows) = lbf.min_flow(0, 3).unwrap();
+        assert_eq!(value, Real(2));
+        assert_eq!(flows, vec![Real(2), Real(0), Real(2), Real(0), Real(0)]);
+        // unbounded
+        lbf.add_edge(0, 3, 0, Inf);
+        assert_eq!(lbf.max_flow(0, 3).unwrap().0, Inf);
+        // 1 supplies 2, which 2 consumes
+        lbf.add_demand(1, -2);
+        lbf.add_demand(2, 2);
+        assert_eq!(lbf.min_flow(0, 3).unwrap().0, Real(2));
+        assert_eq!(lbf.max_flow(0, 3).unwrap().0, Inf);
+        assert!(lbf.circulation().is_none());
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for _ in 0..100 {
+            let n = 4;
+            let (s, t) = (0, n - 1);
+            let m = rand.gen::<usize>() % 5 + 1;
+            let mut lbf = LowerBoundedFlow::new(n);
+            let mut edges = vec![];
+            for _ in 0..m {
+                let u = rand.gen::<usize>() % n;
+                let v = (u + 1 + rand.gen::<usize>() % (n - 1)) % n;
+                let lo = rand.gen::<i64>().rem_euclid(2);
+                let hi = lo + rand.gen::<i64>().rem_euclid(3);
+                lbf.add_edge(u, v, lo, Real(hi));
+                edges.push((u, v, lo, hi));
+            }
+            // balanced demands on the internal vertices
+            let d = rand.gen::<i64>().rem_euclid(5) - 2;
+            let demand = [0, d, -d, 0];
+            lbf.add_demand(1, d);
+            lbf.add_demand(2, -d);
+            // brute force over all flows: (is circulation, value of s -> t flow)
+            let mut circulation = false;
+            let mut values = vec![];
+            let mut flows: Vec<i64> = edges.iter().map(|e| e.2).collect();
+            loop {
+                let mut balance = vec![0; n];
+                for (&(u, v, _, _), &f) in edges.iter().zip(flows.iter()) {
+                    balance[u] -= f;
+                    balance[v] += f;
+                }
+                if balance == demand {
+                    circulation = true;
+                }
+                if (1..n - 1).all(|v| balance[v] == demand[v]) {
+                    values.push(balance[t]);
+                }
+                // next
+                let i = match (0..m).find(|&i| flows[i] < edges[i].3) {
+                    Some(i) => i,
+                    None => break,
+                };
+                flows[i] += 1;
+                for j in 0..i {
+                    flows[j] = edges[j].2;
+                }
+            }
+            match lbf.circulation() {
+                None => assert!(!circulation),
+                Some(flows) => {
+                    assert!(circulation);
+                    let mut balance = vec![0; n];
+                    for (&(u, v, lo, hi), &f) in edges.iter().zip(flows.iter()) {
+                        assert!(lo <= f && f <= hi);
+                        balance[u] -= f;
+                        balance[v] += f;
+                    }
+                    assert_eq!(balance, demand);
+                }
+            }
+            let max = lbf.max_flow(s, t);
+            let min = lbf.min_flow(s, t);
+            assert_eq!(
+                max.as_ref().map(|r| r.0),
+                values.iter().max().map(|&x| Real(x))
+            );
+            assert_eq!(
+                min.as_ref().map(|r| r.0),
+                values.iter().min().map(|&x| Real(x))
+            );
+            for (_, flows) in max.iter().chain(min.iter()) {
+                let mut balance = vec![0; n];
+                for (&(u, v, lo, hi), &f) in edges.iter().zip(flows.iter()) {
+                    let f = f.unwrap();
+                    assert!(lo <= f && f <= hi);
+                    balance[u] -= f;
+                    balance[v] += f;
+                }
+                assert!((1..n - 1).all(|v| balance[v] == demand[v]));
+            }
+        }
+    }
+}
@@ -0,0 +1,271 @@
+/// Graph - Directed - Min Cost Flow (Primal-Dual) - O(F E log V)
+use crate::algebra::hyper::*;
+use crate::algebra::ring::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinCostFlowEdge<X> {
+    pub from: usize,
+    pub to: usize,
+    pub cap: X,
+    pub flow: X,
+    pub cost: X,
+}
+
+#[derive(Debug, Clone)]
+struct Edge<X> {
+    to: usize,
+    rev: usize, // index of the reverse edge in g[to]
+    cap: X,     // residual
+    cost: X,
+}
+
+/// (vertex, edge index) of the previous on a shortest path
+type Prev = Option<(usize, usize)>;
+
+#[derive(Debug, Clone)]
+pub struct MinCostFlow<X> {
+    size: usize,
+    g: Vec<Vec<Edge<X>>>,
+    pos: Vec<(usize, usize)>, // edge id -> (from, index in g[from])
+    potential: Option<(usize, Vec<Hyper<X>>)>, // (source, potential) to resume
+}
+impl<X: std::fmt::Debug + Copy + Ord + Ring> MinCostFlow<X> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            g: vec![vec![]; size],
+            pos: vec![],
+            potential: None,
+        }
+    }
+    /// Add an edge u -> v, and returns its id; cost can be negative
+    pub fn add_edge(&mut self, u: usize, v: usize, cap: X, cost: X) -> usize {
+        assert!(u != v, "self loops are not supported");
+        let id = self.pos.len();
+        self.pos.push((u, self.g[u].len()));
+        let (i, j) = (self.g[u].len(), self.g[v].len());
+        self.g[u].push(Edge {
+            to: v,
+            rev: j,
+            cap,
+            cost,
+        });
+        self.g[v].push(Edge {
+            to: u,
+            rev: i,
+            cap: X::zero(),
+            cost: -cost,
+        });
+        self.potential = None;
+        id
+    }
+    pub fn edge(&self, id: usize) -> MinCostFlowEdge<X> {
+        let (u, i) = self.pos[id];
+        let e = &self.g[u][i];
+        let r = &self.g[e.to][e.rev];
+        MinCostFlowEdge {
+            from: u,
+            to: e.to,
+            cap: e.cap + r.cap,
+            flow: r.cap,
+            cost: e.cost,
+        }
+    }
+    pub fn edges(&self) -> Vec<MinCostFlowEdge<X>> {
+        (0..self.pos.len()).map(|id| self.edge(id)).collect()
+    }
+    /// Shortest distances from s on the residual graph, by Bellman-Ford (negative costs are OK)
+    fn bellman_ford(&self, s: usize) -> Vec<Hyper<X>> {
+        let mut dist = vec![Hyper::Inf; self.size];
+        dist[s] = Hyper::Real(X::zero());
+        for _ in 0..self.size {
+            let mut updated = false;
+            for u in 0..self.size {
+                if dist[u] == Hyper::Inf {
+                    continue;
+                }
+                for e in self.g[u].iter() {
+                    if e.cap > X::zero() && dist[e.to] > dist[u] + e.cost {
+                        dist[e.to] = dist[u] + e.cost;
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                return dist;
+            }
+        }
+        panic!("negative cycle");
+    }
+    /// Shortest distances with the reduced costs, and the previous (vertex, edge index)
+    fn dijkstra(&self, s: usize, h: &[Hyper<X>]) -> (Vec<Hyper<X>>, Vec<Prev>) {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+        let mut dist = vec![Hyper::Inf; self.size];
+        let mut prev = vec![None; self.size];
+        let mut q = BinaryHeap::new();
+        dist[s] = Hyper::Real(X::zero());
+        q.push((Reverse(dist[s]), s));
+        while let Some((Reverse(d), u)) = q.pop() {
+            if dist[u] < d {
+                continue;
+            }
+            for (i, e) in self.g[u].iter().enumerate() {
+                if e.cap <= X::zero() {
+                    continue;
+                }
+                // reduced cost >= 0
+                let c = dist[u] + e.cost + h[u] - h[e.to];
+                if dist[e.to] > c {
+                    dist[e.to] = c;
+                    prev[e.to] = Some((u, i));
+                    q.push((Reverse(c), e.to));
+                }
+            }
+        }
+        (dist, prev)
+    }
+    /// (flow, cost) of the min cost flow s -> t, with flow <= limit (Hyper::Inf for the max flow)
+    pub fn flow(&mut self, s: usize, t: usize, limit: Hyper<X>) -> (X, X) {
+        *self.slope(s, t, limit).last().unwrap()
+    }
+    /// Breakpoints (flow, cost) of the piecewise linear convex function, from (0, 0)
+    pub fn slope(&mut self, s: usize, t: usize, limit: Hyper<X>) -> Vec<(X, X)> {
+        assert!(s != t);
+        let mut h = match self.potential.take() {
+            Some((source, h)) if source == s => h,
+            _ => self.bellman_ford(s),
+        };
+        let mut flow = X::zero();
+        let mut cost = X::zero();
+        let mut result = vec![(flow, cost)];
+        let mut prev_unit: Option<X> = None;
+        while Hyper::Real(flow) < limit {
+            let (dist, prev) = self.dijkstra(s, &h);
+            if dist[t] == Hyper::Inf {
+                break;
+            }
+            for v in 0..self.size {
+                if dist[v] != Hyper::Inf {
+                    h[v] += dist[v];
+                }
+            }
+            let mut d = Hyper::Inf;
+            let mut v = t;
+            while let Some((u, i)) = prev[v] {
+                d = std::cmp::min(d, Hyper::Real(self.g[u][i].cap));
+                v = u;
+            }
+            let d = std::cmp::min(d, limit - Hyper::Real(flow)).unwrap();
+            let mut v = t;
+            while let Some((u, i)) = prev[v] {
+                let rev = self.g[u][i].rev;
+                self.g[u][i].cap = self.g[u][i].cap - d;
+                self.g[v][rev].cap = self.g[v][rev].cap + d;
+                v = u;
+            }
+            let unit = h[t].unwrap();
+            flow = flow + d;
+            cost = cost + d * unit;
+            if prev_unit == Some(unit) {
+                result.pop();
+            }
+            result.push((flow, cost));
+            prev_unit = Some(unit);
+        }
+        self.potential = Some((s, h));
+        result
+    }
+}
+
+#[cfg(test)]
+mod test_min_cost_flow {
+    use crate::algebra::hyper::Hyper::*;
+    use crate::graph::directed::min_cost_flow::*;
+    use crate::num::random::xorshift::*;
+
+    #[test]
+    fn test_slope() {
+        let mut mcf = MinCostFlow::new(4);
+        let e0 = mcf.add_edge(0, 1, 2_i64, 1);
+        let e1 = mcf.add_edge(0, 2, 1, 2);
+        let e2 = mcf.add_edge(1, 2, 1, 1);
+        let e3 = mcf.add_edge(1, 3, 1, 3);
+        let e4 = mcf.add_edge(2, 3, 2, 1);
+        assert_eq!(mcf.slope(0, 3, Inf), vec![(0, 0), (2, 6), (3, 10)]);
+        assert_eq!(mcf.edge(e0).flow, 2);
+        assert_eq!(mcf.edge(e1).flow, 1);
+        assert_eq!(mcf.edge(e2).flow, 1);
+        assert_eq!(mcf.edge(e3).flow, 1);
+        assert_eq!(
+            mcf.edge(e4),
+            MinCostFlowEdge {
+                from: 2,
+                to: 3,
+                cap: 2,
+                flow: 2,
+                cost: 1
+            }
+        );
+        // nothing more
+        assert_eq!(mcf.flow(0, 3, Inf), (0, 0));
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut mcf = MinCostFlow::new(3);
+        mcf.add_edge(0, 1, 10_i128, 2);
+        mcf.add_edge(1, 2, 10, 3);
+        assert_eq!(mcf.flow(0, 2, Real(4)), (4, 20));
+        assert_eq!(mcf.flow(0, 2, Real(4)), (4, 20));
+        assert_eq!(mcf.flow(0, 2, Inf), (2, 10));
+    }
+
+    #[test]
+    fn test_assignment_negative() {
+        // max weight matching = min cost with negated weights
+        let mut rand = XorShift::new();
+        for _ in 0..10 {
+            let n = 5;
+            let w: Vec<Vec<i64>> = (0..n)
+                .map(|_| (0..n).map(|_| rand.gen::<i64>() % 100).collect())
+                .collect();
+            let mut mcf = MinCostFlow::new(2 * n + 2);
+            let (s, t) = (2 * n, 2 * n + 1);
+            for (i, row) in w.iter().enumerate() {
+                mcf.add_edge(s, i, 1, 0);
+                mcf.add_edge(n + i, t, 1, 0);
+                for (j, &wij) in row.iter().enumerate() {
+                    mcf.add_edge(i, n + j, 1, -wij);
+                }
+            }
+            let (flow, cost) = mcf.flow(s, t, Real(n as i64));
+            // brute force over permutations
+            let mut best = i64::MIN;
+            let mut perm: Vec<usize> = (0..n).collect();
+            loop {
+                best = best.max((0..n).map(|i| w[i][perm[i]]).sum());
+                // next permutation
+                let i = match (1..n).rev().find(|&i| perm[i - 1] < perm[i]) {
+                    Some(i) => i,
+                    None => break,
+                };
+                let j = (i..n).rev().find(|&j| perm[i - 1] < perm[j]).unwrap();
+                perm.swap(i - 1, j);
+                perm[i..].reverse();
+            }
+            assert_eq!(flow, n as i64);
+            assert_eq!(-cost, best);
+            // flow conservation
+            let mut balance = vec![0; 2 * n + 2];
+            for e in mcf.edges() {
+                assert!(e.flow >= 0 && e.flow <= e.cap);
+                balance[e.from] -= e.flow;
+                balance[e.to] += e.flow;
+            }
+            assert_eq!(balance[s], -(n as i64));
+            assert_eq!(balance[t], n as i64);
+            assert!(balance[..2 * n].iter().all(|&b| b == 0));
+        }
+    }
+}
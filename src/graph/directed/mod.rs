@@ -1,4 +1,7 @@
 pub mod dinic;
+pub mod flow_edge;
 pub mod ford_fulkerson;
+pub mod lower_bounded_flow;
+pub mod min_cost_flow;
 pub mod scc;
 pub mod topological_sort;
@@ -0,0 +1,73 @@
+/// Graph - Tree - Euler Tour
+#[derive(Debug, Clone)]
+pub struct EulerTour {
+    /// Vertices in the order of entering and leaving (each vertex appears twice)
+    pub order: Vec<usize>,
+    /// order[tin[u]] = u on entering
+    pub tin: Vec<usize>,
+    /// order[tout[u]] = u on leaving; the subtree of u is order[tin[u]..=tout[u]]
+    pub tout: Vec<usize>,
+    pub parent: Vec<Option<usize>>,
+}
+impl EulerTour {
+    /// Both of undirected and directed (Parent -> Child) are OK
+    pub fn new(tree: &[Vec<usize>], root: usize) -> Self {
+        let n = tree.len();
+        let mut order = Vec::with_capacity(2 * n);
+        let mut tin = vec![0; n];
+        let mut tout = vec![0; n];
+        let mut parent = vec![None; n];
+        let mut visited = vec![false; n];
+        let mut stack = vec![(root, 0)];
+        visited[root] = true;
+        order.push(root);
+        while let Some((u, i)) = stack.pop() {
+            if i < tree[u].len() {
+                stack.push((u, i + 1));
+                let v = tree[u][i];
+                if !visited[v] {
+                    visited[v] = true;
+                    parent[v] = Some(u);
+                    tin[v] = order.len();
+                    order.push(v);
+                    stack.push((v, 0));
+                }
+            } else {
+                tout[u] = order.len();
+                order.push(u);
+            }
+        }
+        Self {
+            order,
+            tin,
+            tout,
+            parent,
+        }
+    }
+    /// Whether u is an ancestor of v (or u = v)
+    pub fn is_ancestor(&self, u: usize, v: usize) -> bool {
+        self.tin[u] <= self.tin[v] && self.tout[v] <= self.tout[u]
+    }
+}
+
+#[cfg(test)]
+mod test_euler_tour {
+    use crate::graph::tree::euler_tour::*;
+
+    #[test]
+    fn it_works() {
+        //     0
+        //    / \
+        //   1   2
+        //  / \
+        // 3   4
+        let tree = vec![vec![1, 2], vec![0, 3, 4], vec![0], vec![1], vec![1]];
+        let tour = EulerTour::new(&tree, 0);
+        assert_eq!(tour.order, vec![0, 1, 3, 3, 4, 4, 1, 2, 2, 0]);
+        assert_eq!(tour.tin, vec![0, 1, 7, 2, 4]);
+        assert_eq!(tour.tout, vec![9, 6, 8, 3, 5]);
+        assert_eq!(tour.parent, vec![None, Some(0), Some(0), Some(1), Some(1)]);
+        assert!(tour.is_ancestor(1, 4));
+        assert!(!tour.is_ancestor(2, 4));
+    }
+}
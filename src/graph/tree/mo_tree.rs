@@ -0,0 +1,164 @@
+/// Graph - Tree - Mo's Algorithm on Tree (path queries)
+use crate::algorithm::mo::*;
+use crate::graph::tree::euler_tour::*;
+use crate::graph::tree::lca::*;
+
+pub trait MoTreeState {
+    type Answer;
+    /// Add the vertex v into the path
+    fn add(&mut self, v: usize);
+    /// Remove the vertex v from the path
+    fn remove(&mut self, v: usize);
+    fn answer(&self) -> Self::Answer;
+}
+
+/// Answers for queries of paths u-v (both ends inclusive)
+pub fn mo_tree<S: MoTreeState>(
+    state: &mut S,
+    tree: &[Vec<usize>],
+    root: usize,
+    queries: &[(usize, usize)],
+) -> Vec<S::Answer> {
+    let n = tree.len();
+    let tour = EulerTour::new(tree, root);
+    let children: Vec<Vec<usize>> = {
+        let mut children = vec![vec![]; n];
+        for v in 0..n {
+            if let Some(u) = tour.parent[v] {
+                children[u].push(v);
+            }
+        }
+        children
+    };
+    let lca = LCA::new(&children, root);
+    // the path u-v = vertices appearing once in order[range], (and the lca if any)
+    let (ranges, extras): (Vec<std::ops::Range<usize>>, Vec<Option<usize>>) = queries
+        .iter()
+        .map(|&(u, v)| {
+            let (u, v) = if tour.tin[u] <= tour.tin[v] {
+                (u, v)
+            } else {
+                (v, u)
+            };
+            let w = lca.get(u, v);
+            if w == u {
+                (tour.tin[u]..tour.tin[v] + 1, None)
+            } else {
+                (tour.tout[u]..tour.tin[v] + 1, Some(w))
+            }
+        })
+        .unzip();
+    let mut active = vec![false; n];
+    let mut toggle = |state: &mut S, v: usize| {
+        if active[v] {
+            state.remove(v);
+        } else {
+            state.add(v);
+        }
+        active[v] = !active[v];
+    };
+    let mut answers: Vec<Option<S::Answer>> = (0..queries.len()).map(|_| None).collect();
+    let (mut l, mut r) = (0, 0);
+    for i in hilbert_sort(&ranges) {
+        let q = &ranges[i];
+        while l > q.start {
+            l -= 1;
+            toggle(state, tour.order[l]);
+        }
+        while r < q.end {
+            toggle(state, tour.order[r]);
+            r += 1;
+        }
+        while l < q.start {
+            toggle(state, tour.order[l]);
+            l += 1;
+        }
+        while r > q.end {
+            r -= 1;
+            toggle(state, tour.order[r]);
+        }
+        if let Some(w) = extras[i] {
+            state.add(w);
+            answers[i] = Some(state.answer());
+            state.remove(w);
+        } else {
+            answers[i] = Some(state.answer());
+        }
+    }
+    answers.into_iter().map(|a| a.unwrap()).collect()
+}
+
+#[cfg(test)]
+mod test_mo_tree {
+    use crate::graph::tree::mo_tree::*;
+    use crate::num::random::xorshift::*;
+
+    /// Number of distinct colors on the path
+    struct Distinct {
+        color: Vec<usize>,
+        count: Vec<usize>,
+        distinct: usize,
+    }
+    impl MoTreeState for Distinct {
+        type Answer = usize;
+        fn add(&mut self, v: usize) {
+            self.count[self.color[v]] += 1;
+            if self.count[self.color[v]] == 1 {
+                self.distinct += 1;
+            }
+        }
+        fn remove(&mut self, v: usize) {
+            self.count[self.color[v]] -= 1;
+            if self.count[self.color[v]] == 0 {
+                self.distinct -= 1;
+            }
+        }
+        fn answer(&self) -> usize {
+            self.distinct
+        }
+    }
+
+    #[test]
+    fn test_distinct() {
+        let mut rand = XorShift::new();
+        let n = 100;
+        let mut tree = vec![vec![]; n];
+        let mut parent = vec![None; n];
+        for v in 1..n {
+            let u = rand.gen::<usize>() % v;
+            tree[u].push(v);
+            tree[v].push(u);
+            parent[v] = Some(u);
+        }
+        let color: Vec<usize> = (0..n).map(|_| rand.gen::<usize>() % 10).collect();
+        let queries: Vec<(usize, usize)> = (0..200)
+            .map(|_| (rand.gen::<usize>() % n, rand.gen::<usize>() % n))
+            .collect();
+        let mut state = Distinct {
+            color: color.clone(),
+            count: vec![0; 10],
+            distinct: 0,
+        };
+        let answers = mo_tree(&mut state, &tree, 0, &queries);
+        let ancestors = |u: usize| {
+            let mut path = vec![u];
+            while let Some(p) = parent[*path.last().unwrap()] {
+                path.push(p);
+            }
+            path
+        };
+        for (&(u, v), &ans) in queries.iter().zip(answers.iter()) {
+            let (pu, pv) = (ancestors(u), ancestors(v));
+            let mut set = std::collections::HashSet::new();
+            for &w in pu.iter().filter(|w| !pv.contains(w)) {
+                set.insert(color[w]);
+            }
+            for &w in pv.iter().filter(|w| !pu.contains(w)) {
+                set.insert(color[w]);
+            }
+            let lca = *pu.iter().find(|w| pv.contains(w)).unwrap();
+            set.insert(color[lca]);
+            assert_eq!(ans, set.len());
+        }
+    }
+}
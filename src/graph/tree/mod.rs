@@ -1,2 +1,4 @@
 pub mod diameter;
+pub mod euler_tour;
 pub mod lca;
+pub mod mo_tree;
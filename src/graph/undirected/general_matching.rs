@@ -0,0 +1,220 @@
+/// Graph - Undirected - Maximum Matching on General Graph (Edmonds' Blossom) - O(V^3)
+use crate::algebra::modint::*;
+use crate::mint; // IGNORE
+use crate::num::random::xorshift::*;
+
+struct Blossom<'a> {
+    neigh: &'a [Vec<usize>],
+    mate: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>, // in the alternating tree
+    base: Vec<usize>,           // of the blossom containing the vertex
+    used: Vec<bool>,
+    in_blossom: Vec<bool>,
+    queue: std::collections::VecDeque<usize>,
+}
+impl Blossom<'_> {
+    fn lca(&self, a: usize, b: usize) -> usize {
+        let mut visited = vec![false; self.neigh.len()];
+        let mut a = a;
+        loop {
+            a = self.base[a];
+            visited[a] = true;
+            match self.mate[a] {
+                Some(m) => a = self.parent[m].unwrap(),
+                None => break,
+            }
+        }
+        let mut b = b;
+        loop {
+            b = self.base[b];
+            if visited[b] {
+                return b;
+            }
+            b = self.parent[self.mate[b].unwrap()].unwrap();
+        }
+    }
+    fn mark_path(&mut self, v: usize, b: usize, child: usize) {
+        let (mut v, mut child) = (v, child);
+        while self.base[v] != b {
+            let m = self.mate[v].unwrap();
+            self.in_blossom[self.base[v]] = true;
+            self.in_blossom[self.base[m]] = true;
+            self.parent[v] = Some(child);
+            child = m;
+            v = self.parent[m].unwrap();
+        }
+    }
+    /// The end of an augmenting path from root, if exists
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.neigh.len();
+        self.used = vec![false; n];
+        self.parent = vec![None; n];
+        self.base = (0..n).collect();
+        self.used[root] = true;
+        self.queue.clear();
+        self.queue.push_back(root);
+        while let Some(v) = self.queue.pop_front() {
+            for &to in self.neigh[v].iter() {
+                if self.base[v] == self.base[to] || self.mate[v] == Some(to) {
+                    continue;
+                }
+                #[allow(clippy::unnecessary_map_or)] // is_some_and needs Rust 1.70
+                let is_odd_cycle =
+                    to == root || self.mate[to].map_or(false, |m| self.parent[m].is_some());
+                if is_odd_cycle {
+                    let b = self.lca(v, to);
+                    self.in_blossom = vec![false; n];
+                    self.mark_path(v, b, to);
+                    self.mark_path(to, b, v);
+                    for i in 0..n {
+                        if self.in_blossom[self.base[i]] {
+                            self.base[i] = b;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                self.queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.parent[to].is_none() {
+                    self.parent[to] = Some(v);
+                    match self.mate[to] {
+                        None => return Some(to),
+                        Some(m) => {
+                            self.used[m] = true;
+                            self.queue.push_back(m);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Mate of each vertex in a maximum matching; neigh is undirected
+pub fn general_matching(neigh: &[Vec<usize>]) -> Vec<Option<usize>> {
+    let n = neigh.len();
+    let mut blossom = Blossom {
+        neigh,
+        mate: vec![None; n],
+        parent: vec![None; n],
+        base: (0..n).collect(),
+        used: vec![false; n],
+        in_blossom: vec![false; n],
+        queue: std::collections::VecDeque::new(),
+    };
+    for root in 0..n {
+        if blossom.mate[root].is_some() {
+            continue;
+        }
+        let mut v = blossom.find_path(root);
+        while let Some(t) = v {
+            let pv = blossom.parent[t].unwrap();
+            let ppv = blossom.mate[pv];
+            blossom.mate[t] = Some(pv);
+            blossom.mate[pv] = Some(t);
+            v = ppv;
+        }
+    }
+    blossom.mate
+}
+
+/// Size of the maximum matching, by the rank of a random Tutte matrix;
+/// correct with high probability over the weights drawn from rand
+/// (may underestimate with probability <= n / 998244353)
+pub fn matching_size_tutte(neigh: &[Vec<usize>], rand: &mut XorShift) -> usize {
+    let n = neigh.len();
+    let mut a = vec![vec![mint!(0); n]; n];
+    for (u, vs) in neigh.iter().enumerate() {
+        for &v in vs.iter() {
+            if u < v {
+                let x = mint!(rand.gen::<i128>().rem_euclid(MOD_998244353 - 1) + 1);
+                a[u][v] = x;
+                a[v][u] = -x;
+            }
+        }
+    }
+    // rank by Gaussian elimination
+    let mut rank = 0;
+    for col in 0..n {
+        let pivot = match (rank..n).find(|&i| a[i][col] != mint!(0)) {
+            Some(i) => i,
+            None => continue,
+        };
+        a.swap(rank, pivot);
+        let row = a[rank].clone();
+        let inv = row[col].inv();
+        for r in a[rank + 1..].iter_mut() {
+            if r[col] == mint!(0) {
+                continue;
+            }
+            let c = r[col] * inv;
+            for (x, &y) in r[col..].iter_mut().zip(row[col..].iter()) {
+                *x -= y * c;
+            }
+        }
+        rank += 1;
+    }
+    rank / 2
+}
+
+#[cfg(test)]
+mod test_general_matching {
+    use crate::graph::undirected::general_matching::*;
+
+    #[test]
+    fn test_odd_cycle() {
+        // 5-cycle with a pendant: 0-1-2-3-4-0, 4-5
+        let neigh = vec![
+            vec![1, 4],
+            vec![0, 2],
+            vec![1, 3],
+            vec![2, 4],
+            vec![3, 0, 5],
+            vec![4],
+        ];
+        let mate = general_matching(&neigh);
+        assert_eq!(mate.iter().filter(|m| m.is_some()).count(), 6);
+        assert_eq!(mate[5], Some(4));
+        assert_eq!(matching_size_tutte(&neigh, &mut XorShift::from_seed(1)), 3);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for _ in 0..100 {
+            let n = rand.gen::<usize>() % 9 + 1;
+            let mut neigh = vec![vec![]; n];
+            for u in 0..n {
+                for v in u + 1..n {
+                    if rand.gen::<usize>() % 3 == 2 {
+                        neigh[u].push(v);
+                        neigh[v].push(u);
+                    }
+                }
+            }
+            let mate = general_matching(&neigh);
+            for u in 0..n {
+                if let Some(v) = mate[u] {
+                    assert_eq!(mate[v], Some(u));
+                    assert!(neigh[u].contains(&v));
+                }
+            }
+            let size = mate.iter().filter(|m| m.is_some()).count() / 2;
+            // brute force over subsets of vertices
+            let mut dp = vec![0; 1 << n];
+            for s in 1..1_usize << n {
+                let u = s.trailing_zeros() as usize;
+                let rest = s & !(1 << u);
+                dp[s] = dp[rest];
+                for &v in neigh[u].iter() {
+                    if rest >> v & 1 == 1 {
+                        dp[s] = dp[s].max(dp[rest & !(1 << v)] + 1);
+                    }
+                }
+            }
+            assert_eq!(size, dp[(1 << n) - 1]);
+            assert_eq!(matching_size_tutte(&neigh, &mut rand), size);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+/// Graph - Undirected - Bipartite Matching (Hopcroft-Karp) - O(E sqrt(V))
+#[derive(Debug, Clone)]
+pub struct HopcroftKarp {
+    neigh: Vec<Vec<usize>>,
+    /// Matched right vertex for each left vertex
+    pub mate_left: Vec<Option<usize>>,
+    /// Matched left vertex for each right vertex
+    pub mate_right: Vec<Option<usize>>,
+}
+impl HopcroftKarp {
+    /// neigh[u] is the list of right vertices (0..right) adjacent to the left vertex u
+    pub fn new(right: usize, neigh: &[Vec<usize>]) -> Self {
+        let left = neigh.len();
+        let mut hk = Self {
+            neigh: neigh.to_vec(),
+            mate_left: vec![None; left],
+            mate_right: vec![None; right],
+        };
+        loop {
+            let dist = hk.levelize();
+            let mut iter = vec![0; left];
+            let mut updated = false;
+            for u in 0..left {
+                if hk.mate_left[u].is_none() && hk.augment(u, &dist, &mut iter) {
+                    updated = true;
+                }
+            }
+            if !updated {
+                break;
+            }
+        }
+        hk
+    }
+    /// Distances of left vertices from free left vertices on alternating paths
+    fn levelize(&self) -> Vec<usize> {
+        let left = self.neigh.len();
+        let mut dist = vec![usize::MAX; left];
+        let mut q = std::collections::VecDeque::new();
+        for (u, m) in self.mate_left.iter().enumerate() {
+            if m.is_none() {
+                dist[u] = 0;
+                q.push_back(u);
+            }
+        }
+        while let Some(u) = q.pop_front() {
+            for &v in self.neigh[u].iter() {
+                if let Some(w) = self.mate_right[v] {
+                    if dist[w] == usize::MAX {
+                        dist[w] = dist[u] + 1;
+                        q.push_back(w);
+                    }
+                }
+            }
+        }
+        dist
+    }
+    fn augment(&mut self, u: usize, dist: &[usize], iter: &mut [usize]) -> bool {
+        while iter[u] < self.neigh[u].len() {
+            let v = self.neigh[u][iter[u]];
+            iter[u] += 1;
+            let ok = match self.mate_right[v] {
+                None => true,
+                Some(w) => dist[w] == dist[u] + 1 && self.augment(w, dist, iter),
+            };
+            if ok {
+                self.mate_left[u] = Some(v);
+                self.mate_right[v] = Some(u);
+                return true;
+            }
+        }
+        false
+    }
+    /// Size of the maximum matching
+    pub fn len(&self) -> usize {
+        self.mate_left.iter().filter(|m| m.is_some()).count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Matched pairs (left, right)
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        self.mate_left
+            .iter()
+            .enumerate()
+            .filter_map(|(u, &m)| m.map(|v| (u, v)))
+            .collect()
+    }
+    /// (left, right) vertices reachable from free left vertices on alternating paths
+    fn alternating_reachable(&self) -> (Vec<bool>, Vec<bool>) {
+        let mut left = vec![false; self.mate_left.len()];
+        let mut right = vec![false; self.mate_right.len()];
+        let mut stack = vec![];
+        for (u, m) in self.mate_left.iter().enumerate() {
+            if m.is_none() {
+                left[u] = true;
+                stack.push(u);
+            }
+        }
+        while let Some(u) = stack.pop() {
+            for &v in self.neigh[u].iter() {
+                if right[v] {
+                    continue;
+                }
+                right[v] = true;
+                if let Some(w) = self.mate_right[v] {
+                    if !left[w] {
+                        left[w] = true;
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+        (left, right)
+    }
+    /// Minimum vertex cover (left vertices, right vertices) by Konig's theorem
+    pub fn min_vertex_cover(&self) -> (Vec<usize>, Vec<usize>) {
+        let (left, right) = self.alternating_reachable();
+        (
+            (0..left.len()).filter(|&u| !left[u]).collect(),
+            (0..right.len()).filter(|&v| right[v]).collect(),
+        )
+    }
+    /// Maximum independent set (left vertices, right vertices); the complement of the min vertex cover
+    pub fn max_independent_set(&self) -> (Vec<usize>, Vec<usize>) {
+        let (left, right) = self.alternating_reachable();
+        (
+            (0..left.len()).filter(|&u| left[u]).collect(),
+            (0..right.len()).filter(|&v| !right[v]).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_hopcroft_karp {
+    use crate::algebra::hyper::Hyper::*;
+    use crate::graph::directed::dinic::*;
+    use crate::graph::undirected::hopcroft_karp::*;
+    use crate::num::random::xorshift::*;
+
+    #[test]
+    fn it_works() {
+        let hk = HopcroftKarp::new(3, &[vec![0, 1], vec![0], vec![0, 2], vec![]]);
+        assert_eq!(hk.len(), 3);
+        assert_eq!(hk.pairs(), vec![(0, 1), (1, 0), (2, 2)]);
+        assert_eq!(
+            hk.min_vertex_cover().0.len() + hk.min_vertex_cover().1.len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for _ in 0..50 {
+            let left = rand.gen::<usize>() % 10 + 1;
+            let right = rand.gen::<usize>() % 10 + 1;
+            let neigh: Vec<Vec<usize>> = (0..left)
+                .map(|_| {
+                    (0..right)
+                        .filter(|_| rand.gen::<usize>() % 4 == 3)
+                        .collect()
+                })
+                .collect();
+            let hk = HopcroftKarp::new(right, &neigh);
+            // Dinic
+            let (s, t) = (left + right, left + right + 1);
+            let mut g = vec![vec![]; left + right + 2];
+            for (u, vs) in neigh.iter().enumerate() {
+                g[s].push((u, Real(1_i64)));
+                for &v in vs.iter() {
+                    g[u].push((left + v, Real(1)));
+                }
+            }
+            for v in 0..right {
+                g[left + v].push((t, Real(1)));
+            }
+            let flow = Dinic::new(s, t, &g).maxflow().unwrap() as usize;
+            assert_eq!(hk.len(), flow);
+            for &(u, v) in hk.pairs().iter() {
+                assert!(neigh[u].contains(&v));
+                assert_eq!(hk.mate_right[v], Some(u));
+            }
+            let (cl, cr) = hk.min_vertex_cover();
+            assert_eq!(cl.len() + cr.len(), flow);
+            let (il, ir) = hk.max_independent_set();
+            assert_eq!(il.len() + ir.len(), left + right - flow);
+            for (u, vs) in neigh.iter().enumerate() {
+                for &v in vs.iter() {
+                    assert!(cl.contains(&u) || cr.contains(&v));
+                    assert!(!(il.contains(&u) && ir.contains(&v)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+/// Graph - Undirected - Hungarian Algorithm (Min Weight Perfect Matching on Bipartite Graph) - O(n^2 m)
+use crate::algebra::group_additive::*;
+use crate::algebra::hyper::*;
+
+/// For the n x m cost matrix (n <= m), returns (min cost, assigned column for each row).
+/// For max weight, negate the costs.
+pub fn hungarian<X: Copy + Ord + AGroup>(cost: &[Vec<X>]) -> (X, Vec<usize>) {
+    let n = cost.len();
+    let m = cost.first().map_or(0, |row| row.len());
+    assert!(n <= m);
+    // 1-indexed; column 0 is a dummy
+    let mut u = vec![X::zero(); n + 1];
+    let mut v = vec![X::zero(); m + 1];
+    let mut p = vec![0; m + 1]; // row assigned to the column
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![Hyper::Inf; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = Hyper::Inf;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let cur = Hyper::Real(cost[i0 - 1][j - 1] - u[i0] - v[j]);
+                if cur < minv[j] {
+                    minv[j] = cur;
+                    way[j] = j0;
+                }
+                if minv[j] < delta {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+            let delta = delta.unwrap();
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] = u[p[j]] + delta;
+                    v[j] = v[j] - delta;
+                } else {
+                    minv[j] -= Hyper::Real(delta);
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        while j0 != 0 {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+        }
+    }
+    let mut assignment = vec![0; n];
+    for j in 1..=m {
+        if p[j] > 0 {
+            assignment[p[j] - 1] = j - 1;
+        }
+    }
+    let total = (0..n).map(|i| cost[i][assignment[i]]).sum();
+    (total, assignment)
+}
+
+#[cfg(test)]
+mod test_hungarian {
+    use crate::graph::undirected::hungarian::*;
+    use crate::num::random::xorshift::*;
+
+    #[test]
+    fn it_works() {
+        let cost: Vec<Vec<i64>> = vec![vec![4, 1, 5], vec![2, 0, 5]];
+        assert_eq!(hungarian(&cost), (3, vec![1, 0]));
+        let empty: Vec<Vec<i64>> = vec![];
+        assert_eq!(hungarian(&empty), (0, vec![]));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for _ in 0..50 {
+            let n = rand.gen::<usize>() % 5 + 1;
+            let m = n + rand.gen::<usize>() % 3;
+            let cost: Vec<Vec<i64>> = (0..n)
+                .map(|_| (0..m).map(|_| rand.gen::<i64>() % 100).collect())
+                .collect();
+            let (total, assignment) = hungarian(&cost);
+            let mut sorted = assignment.clone();
+            sorted.sort();
+            sorted.dedup();
+            assert_eq!(sorted.len(), n);
+            assert!(assignment.iter().all(|&j| j < m));
+            assert_eq!(total, (0..n).map(|i| cost[i][assignment[i]]).sum());
+            // brute force over distinct columns; the first n of each permutation of 0..m
+            let mut best = i64::MAX;
+            let mut perm: Vec<usize> = (0..m).collect();
+            loop {
+                best = best.min((0..n).map(|i| cost[i][perm[i]]).sum());
+                let i = match (1..m).rev().find(|&i| perm[i - 1] < perm[i]) {
+                    Some(i) => i,
+                    None => break,
+                };
+                let j = (i..m).rev().find(|&j| perm[i - 1] < perm[j]).unwrap();
+                perm.swap(i - 1, j);
+                perm[i..].reverse();
+            }
+            assert_eq!(total, best);
+        }
+    }
+}
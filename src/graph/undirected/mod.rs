@@ -1,4 +1,7 @@
 pub mod bridges;
 pub mod diameter;
+pub mod general_matching;
+pub mod hopcroft_karp;
+pub mod hungarian;
 pub mod is_bigraph;
 pub mod lowlink;
@@ -1,21 +1,27 @@
 /// Hash - RollingHash
 use crate::algebra::modint::*;
+use crate::algebra::modint_like::*;
 use crate::mint; // IGNORE
 
 #[derive(Debug, Clone)]
-pub struct RollingHash {
-    data: ModInt,
-    base: ModInt,
-    baseinv: ModInt,
+pub struct RollingHash<K = ModInt> {
+    data: K,
+    base: K,
+    baseinv: K,
     length: usize,
-    pow: ModInt,
+    pow: K,
 }
-impl RollingHash {
+impl RollingHash<ModInt> {
     pub fn new() -> Self {
-        let data = mint!(0);
-        let base = mint!(37);
-        let baseinv = base.inv();
-        let pow = mint!(1);
+        Self::with_base(mint!(37))
+    }
+}
+impl<K: ModIntLike> RollingHash<K> {
+    /// Empty hash on the same Z/mZ as `base`
+    pub fn with_base(base: K) -> Self {
+        let data = base.like(0);
+        let baseinv = base.like(1) / base;
+        let pow = base.like(1);
         Self {
             data,
             base,
@@ -25,27 +31,27 @@ impl RollingHash {
         }
     }
     pub fn unwrap(&self) -> i128 {
-        self.data.0
+        self.data.residual()
     }
     pub fn push_back(&mut self, x: i128) {
         self.length += 1;
         self.pow = self.pow * self.base;
-        self.data = self.data * self.base + mint!(x);
+        self.data = self.data * self.base + self.base.like(x);
     }
     pub fn push_front(&mut self, x: i128) {
-        self.data = self.data + self.pow * mint!(x);
+        self.data = self.data + self.pow * self.base.like(x);
         self.length += 1;
         self.pow = self.pow * self.base;
     }
     pub fn pop_back(&mut self, x: i128) {
         self.length -= 1;
         self.pow = self.pow * self.baseinv;
-        self.data = (self.data - mint!(x)) * self.baseinv;
+        self.data = (self.data - self.base.like(x)) * self.baseinv;
     }
     pub fn pop_front(&mut self, x: i128) {
         self.length -= 1;
         self.pow = self.pow * self.baseinv;
-        self.data = self.data - self.pow * mint!(x);
+        self.data = self.data - self.pow * self.base.like(x);
     }
     pub fn concat(&self, other: &Self) -> Self {
         let mut r = self.clone();
@@ -55,9 +61,32 @@ impl RollingHash {
         r
     }
 }
-impl std::cmp::PartialEq for RollingHash {
+impl<K: PartialEq> std::cmp::PartialEq for RollingHash<K> {
     fn eq(&self, other: &Self) -> bool {
         self.length == other.length && self.data == other.data
     }
 }
-impl std::cmp::Eq for RollingHash {}
+impl<K: Eq> std::cmp::Eq for RollingHash<K> {}
+
+#[cfg(test)]
+mod test_rolling {
+    use crate::hash::rolling::*;
+
+    #[test]
+    fn test_static_modint() {
+        use crate::algebra::modint_static::*;
+        let mut x = RollingHash::with_base(ModInt1000000007::new(10_007));
+        let mut y = x.clone();
+        x.push_back(1);
+        x.push_back(2);
+        y.push_front(2);
+        y.push_front(1);
+        assert_eq!(x, y);
+        y.push_back(3);
+        y.pop_front(1);
+        x.pop_front(1);
+        x.push_back(3);
+        assert_eq!(x, y);
+        assert_eq!(x.unwrap(), 2 * 10_007 + 3);
+    }
+}
@@ -1,69 +1,79 @@
 /// Number - Binomial Coefficient on ModInt
 use crate::algebra::modint::*;
+use crate::algebra::modint_like::*;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Binom {
+pub struct Binom<K = ModInt> {
     n: u128,
     k: u128,
-    coeff: ModInt,
+    coeff: K,
 }
 
-impl Binom {
-    pub fn unwrap(&self) -> ModInt {
+impl Binom<ModInt> {
+    /// Calc Binom-Coeff with O(k)
+    pub fn new(n: u128, k: u128, modulo: i128) -> Self {
+        Self::new_like(n, k, ModInt(1, modulo))
+    }
+}
+
+impl<K: ModIntLike> Binom<K> {
+    pub fn unwrap(&self) -> K {
         self.coeff
     }
 
-    /// Calc Binom-Coeff with O(k)
-    pub fn new(n: u128, k: u128, modulo: i128) -> Self {
+    /// Calc Binom-Coeff on the same Z/mZ as `unit` with O(k)
+    pub fn new_like(n: u128, k: u128, unit: K) -> Self {
         if k == 0 {
-            let coeff = ModInt(1, modulo);
+            let coeff = unit.like(1);
             Self { n, k, coeff }
         } else if n < k || n == 0 {
-            let coeff = ModInt(0, modulo);
+            let coeff = unit.like(0);
             Self { n, k, coeff }
         } else if n < k * 2 {
-            let mut m = Self::new(n, n - k, modulo);
+            let mut m = Self::new_like(n, n - k, unit);
             m.k = k;
             m
         } else {
-            let mut c = ModInt(1, modulo);
+            let mut c = unit.like(1);
             for i in 0..k {
-                c *= (n - i) as i128;
-                c /= (k - i) as i128;
+                c = c * unit.like((n - i) as i128);
+                c = c / unit.like((k - i) as i128);
             }
             Self { n, k, coeff: c }
         }
     }
 
     /// Calc `binom(n, k)` with a Hint
-    pub fn new_with_hint(n: u128, k: u128, hint: &Binom) -> Self {
+    pub fn new_with_hint(n: u128, k: u128, hint: &Binom<K>) -> Self {
+        let unit = hint.unwrap().like(1);
         if k == 0 {
-            let coeff = ModInt(1, hint.unwrap().1);
+            let coeff = unit;
             return Self { n, k, coeff };
         }
         if n < k || n == 0 {
-            let coeff = ModInt(0, hint.unwrap().1);
+            let coeff = unit.like(0);
             return Self { n, k, coeff };
         }
         if n == hint.n && k == hint.k {
             return *hint;
         }
+        let frac = |p: u128, q: u128| unit.like(p as i128) / unit.like(q as i128);
         let (n_next, k_next, c_next) = if n < hint.n && k < hint.k {
-            let c = hint.unwrap() * hint.k as i128 / hint.n as i128;
+            let c = hint.unwrap() * frac(hint.k, hint.n);
             (hint.n - 1, hint.k - 1, c)
         } else if n > hint.n && k > hint.k {
-            let c = hint.unwrap() * (hint.n + 1) as i128 / (hint.k + 1) as i128;
+            let c = hint.unwrap() * frac(hint.n + 1, hint.k + 1);
             (hint.n + 1, hint.k + 1, c)
         } else if n > hint.n {
-            let c = hint.unwrap() * (hint.n + 1) as i128 / (hint.n - hint.k + 1) as i128;
+            let c = hint.unwrap() * frac(hint.n + 1, hint.n - hint.k + 1);
             (hint.n + 1, hint.k, c)
         } else if n < hint.n {
-            let c = hint.unwrap() * (hint.n - hint.k) as i128 / hint.n as i128;
+            let c = hint.unwrap() * frac(hint.n - hint.k, hint.n);
             (hint.n - 1, hint.k, c)
         } else if k > hint.k {
-            let c = hint.unwrap() * (hint.n - hint.k) as i128 / (hint.k + 1) as i128;
+            let c = hint.unwrap() * frac(hint.n - hint.k, hint.k + 1);
             (hint.n, hint.k + 1, c)
         } else {
-            let c = hint.unwrap() * hint.k as i128 / (hint.n - hint.k + 1) as i128;
+            let c = hint.unwrap() * frac(hint.k, hint.n - hint.k + 1);
             (hint.n, hint.k - 1, c)
         };
         let nexthint = Binom {
@@ -108,6 +118,22 @@ mod test_binom_modint {
         }
     }
 
+    #[test]
+    fn test_static_modint() {
+        use crate::algebra::modint_static::*;
+        use crate::algebra::monoid::*;
+        type Mint = ModInt1000000007;
+        let c = Binom::new_like(100, 50, Mint::one());
+        assert_eq!(
+            c.unwrap().residual(),
+            Binom::new(100, 50, 1_000_000_007).unwrap().unwrap()
+        );
+        assert_eq!(
+            Binom::new_with_hint(99, 51, &c),
+            Binom::new_like(99, 51, Mint::one())
+        );
+    }
+
     #[test]
     fn test_erroneous() {
         const MOD: i128 = 107;
@@ -1,36 +1,35 @@
 /// Number - Discrete Logarithm
-use crate::algebra::modint::*;
+use crate::algebra::modint_like::*;
 
 // Returns x; pow(a, x) == b
-pub fn dlog(a: ModInt, b: ModInt) -> i128 {
+pub fn dlog<K: ModIntLike>(a: K, b: K) -> i128 {
     let s = {
         let mut s = 1;
-        while s * s <= a.1 {
+        while s * s <= a.modulo() {
             s += 1;
         }
         s - 1
     }; // sqrt(a)
     let mut pows = std::collections::HashMap::new();
-    {
-        let mut pow = ModInt(1, a.1);
-        for i in 0..s {
-            pows.insert(pow.0, i);
-            pow *= a;
-        }
+    let mut pow = a.like(1);
+    for i in 0..s {
+        pows.insert(pow.residual(), i);
+        pow = pow * a;
     }
-    let aa = a.pow(-s);
+    let aa = a.like(1) / pow; // pow(a, -s)
     let mut ac = b;
     for i in 0..s {
-        if pows.contains_key(&ac.0) {
-            return pows[&ac.0] + i * s;
+        if pows.contains_key(&ac.residual()) {
+            return pows[&ac.residual()] + i * s;
         }
-        ac *= aa;
+        ac = ac * aa;
     }
     -1
 }
 
 #[cfg(test)]
 mod test_dlog {
+    use crate::algebra::modint::*;
     use crate::num::dlog::*;
 
     #[test]
@@ -39,6 +38,13 @@ mod test_dlog {
         assert_eq!(dlog(ModInt(2, MOD), ModInt(1024, MOD)), 10);
     }
 
+    #[test]
+    fn test_static_modint() {
+        use crate::algebra::modint_static::*;
+        type Mint = ModInt1000000007;
+        assert_eq!(dlog(Mint::new(5), Mint::new(5).pow(12345)), 12345);
+    }
+
     #[test]
     fn under_modulo() {
         const MOD: i128 = 107;
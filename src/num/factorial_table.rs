@@ -0,0 +1,163 @@
+/// Number - Factorial Table on ModInt (O(1) Binomial Coefficients)
+use crate::algebra::modint::*;
+use crate::algorithm::ntt::*;
+
+#[derive(Debug, Clone)]
+pub struct FactorialTable {
+    modulo: i128,
+    fact: Vec<ModInt>,
+    fact_inv: Vec<ModInt>,
+}
+impl FactorialTable {
+    /// Table of 0!, 1!, .., n! with O(n); requires n < modulo
+    pub fn new(n: usize, modulo: i128) -> Self {
+        assert!((n as i128) < modulo);
+        let mut fact = vec![ModInt(1, modulo); n + 1];
+        for i in 1..=n {
+            fact[i] = fact[i - 1] * i as i128;
+        }
+        let mut fact_inv = vec![ModInt(1, modulo); n + 1];
+        fact_inv[n] = fact[n].inv();
+        for i in (1..=n).rev() {
+            fact_inv[i - 1] = fact_inv[i] * i as i128;
+        }
+        Self {
+            modulo,
+            fact,
+            fact_inv,
+        }
+    }
+    /// n!
+    pub fn fact(&self, n: usize) -> ModInt {
+        self.fact[n]
+    }
+    /// 1/n!
+    pub fn fact_inv(&self, n: usize) -> ModInt {
+        self.fact_inv[n]
+    }
+    /// 1/n, for 0 < n
+    pub fn inv(&self, n: usize) -> ModInt {
+        assert!(n > 0);
+        self.fact_inv[n] * self.fact[n - 1]
+    }
+    /// n C k
+    pub fn binom(&self, n: usize, k: usize) -> ModInt {
+        if k > n {
+            ModInt(0, self.modulo)
+        } else {
+            self.fact[n] * self.fact_inv[k] * self.fact_inv[n - k]
+        }
+    }
+    /// n P k
+    pub fn perm(&self, n: usize, k: usize) -> ModInt {
+        if k > n {
+            ModInt(0, self.modulo)
+        } else {
+            self.fact[n] * self.fact_inv[n - k]
+        }
+    }
+    /// (k_1 + k_2 + ..)! / (k_1! k_2! ..)
+    pub fn multinom(&self, ks: &[usize]) -> ModInt {
+        let n: usize = ks.iter().sum();
+        ks.iter()
+            .fold(self.fact[n], |acc, &k| acc * self.fact_inv[k])
+    }
+    /// n-th Catalan Number, requires 2n <= size of table
+    pub fn catalan(&self, n: usize) -> ModInt {
+        self.binom(2 * n, n) * self.inv(n + 1)
+    }
+    /// Stirling Numbers of the 2nd kind; [S(n, 0), S(n, 1), .., S(n, n)] with O(n log n)
+    pub fn stirling2(&self, n: usize) -> Vec<ModInt> {
+        let a: Vec<ModInt> = (0..=n)
+            .map(|i| {
+                if i % 2 == 0 {
+                    self.fact_inv[i]
+                } else {
+                    -self.fact_inv[i]
+                }
+            })
+            .collect();
+        let b: Vec<ModInt> = (0..=n)
+            .map(|j| ModInt(j as i128, self.modulo).pow(n as i128) * self.fact_inv[j])
+            .collect();
+        let mut s = convolve_any_mod(&a, &b);
+        s.truncate(n + 1);
+        s
+    }
+    /// n C k by Lucas's theorem, for huge n and a small prime modulo p;
+    /// the table should be `FactorialTable::new(p - 1, p)`
+    pub fn binom_lucas(&self, n: u64, k: u64) -> ModInt {
+        let p = self.modulo as u64;
+        assert!(self.fact.len() as u64 >= p);
+        let mut r = ModInt(1, self.modulo);
+        let (mut n, mut k) = (n, k);
+        while k > 0 {
+            r *= self.binom((n % p) as usize, (k % p) as usize);
+            n /= p;
+            k /= p;
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod test_factorial_table {
+    use crate::num::binom_modint::*;
+    use crate::num::factorial_table::*;
+
+    #[test]
+    fn test_binom() {
+        const MOD: i128 = MOD_1000000007;
+        let table = FactorialTable::new(200, MOD);
+        for n in 0..100 {
+            for k in 0..=n + 1 {
+                assert_eq!(
+                    table.binom(n, k),
+                    Binom::new(n as u128, k as u128, MOD).unwrap()
+                );
+            }
+        }
+        assert_eq!(table.perm(5, 2), ModInt(20, MOD));
+        assert_eq!(table.perm(2, 5), ModInt(0, MOD));
+        assert_eq!(table.multinom(&[2, 1, 1]), ModInt(12, MOD));
+        assert_eq!(table.inv(3) * 3, ModInt(1, MOD));
+        assert_eq!(table.fact(10).unwrap(), 3_628_800);
+        assert_eq!(table.fact(10) * table.fact_inv(10), ModInt(1, MOD));
+    }
+
+    #[test]
+    fn test_catalan() {
+        let table = FactorialTable::new(20, MOD_998244353);
+        let expected = [1, 1, 2, 5, 14, 42, 132, 429];
+        for (n, &c) in expected.iter().enumerate() {
+            assert_eq!(table.catalan(n).unwrap(), c);
+        }
+    }
+
+    #[test]
+    fn test_stirling2() {
+        let table = FactorialTable::new(10, MOD_1000000007);
+        let s: Vec<i128> = table.stirling2(5).iter().map(|x| x.unwrap()).collect();
+        assert_eq!(s, vec![0, 1, 15, 25, 10, 1]);
+        let s: Vec<i128> = table.stirling2(0).iter().map(|x| x.unwrap()).collect();
+        assert_eq!(s, vec![1]);
+    }
+
+    #[test]
+    fn test_lucas() {
+        use crate::num::binom_pascal::*;
+        const P: i128 = 7;
+        let table = FactorialTable::new(P as usize - 1, P);
+        let pascal = pascal_triagle::<i64>(60);
+        for (n, row) in pascal.iter().enumerate().skip(1) {
+            for (k, &c) in row.iter().enumerate().take(n + 1) {
+                assert_eq!(
+                    table.binom_lucas(n as u64, k as u64),
+                    ModInt::new(c as i128, P)
+                );
+            }
+        }
+        assert_eq!(table.binom_lucas(0, 0), ModInt(1, P));
+        assert_eq!(table.binom_lucas(3, 5), ModInt(0, P));
+    }
+}
@@ -3,6 +3,7 @@ pub mod binom_pascal;
 pub mod chinese_remainder_theorem;
 pub mod dlog;
 pub mod euler_phi;
+pub mod factorial_table;
 pub mod float;
 pub mod gcd;
 pub mod gcd_ex;
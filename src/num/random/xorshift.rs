@@ -1,11 +1,17 @@
 /// Random Number - Xor-Shift Algorithm
 use crate::num::random::fromu64::*;
 
+#[derive(Debug, Clone)]
 pub struct XorShift(u64);
 impl XorShift {
     pub fn new() -> Self {
         XorShift(88_172_645_463_325_252)
     }
+    /// seed should be non-zero
+    pub fn from_seed(seed: u64) -> Self {
+        assert!(seed != 0);
+        XorShift(seed)
+    }
     fn next(&mut self) -> u64 {
         let mut x = self.0;
         x = x ^ (x << 13);
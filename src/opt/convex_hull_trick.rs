@@ -0,0 +1,123 @@
+/// Optimization - Convex Hull Trick (monotone slopes)
+use crate::algebra::ring::*;
+
+/// Whether l2 = (a2, b2) is not needed for the min of l1, l3 (with slopes a1 > a2 > a3)
+pub fn useless_line<K: Ring + Copy + Ord>(l1: (K, K), l2: (K, K), l3: (K, K)) -> bool {
+    let (a1, b1) = l1;
+    let (a2, b2) = l2;
+    let (a3, b3) = l3;
+    (b2 - b1) * (a2 - a3) >= (b3 - b2) * (a1 - a2)
+}
+
+/// Min of lines a*x + b, where lines are added in the non-increasing order of slopes.
+/// For max, add (-a, -b) and negate the results.
+#[derive(Debug, Clone)]
+pub struct MonotoneCHT<K> {
+    lines: std::collections::VecDeque<(K, K)>,
+}
+impl<K: Ring + Copy + Ord> MonotoneCHT<K> {
+    pub fn new() -> Self {
+        Self {
+            lines: std::collections::VecDeque::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+    /// Add a*x + b; a should be <= any slope added before
+    pub fn add_line(&mut self, a: K, b: K) {
+        if let Some(&(a0, b0)) = self.lines.back() {
+            assert!(a <= a0, "slopes should be non-increasing");
+            if a == a0 {
+                if b0 <= b {
+                    return;
+                }
+                self.lines.pop_back();
+            }
+        }
+        while self.lines.len() >= 2 {
+            let n = self.lines.len();
+            if useless_line(self.lines[n - 2], self.lines[n - 1], (a, b)) {
+                self.lines.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.lines.push_back((a, b));
+    }
+    fn eval(line: (K, K), x: K) -> K {
+        line.0 * x + line.1
+    }
+    /// min a*x + b, with O(log n)
+    pub fn query(&self, x: K) -> Option<K> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        // the first i s.t. lines[i] <= lines[i + 1] at x
+        let (mut left, mut right) = (0, self.lines.len() - 1);
+        while left < right {
+            let mid = (left + right) / 2;
+            if Self::eval(self.lines[mid], x) <= Self::eval(self.lines[mid + 1], x) {
+                right = mid;
+            } else {
+                left = mid + 1;
+            }
+        }
+        Some(Self::eval(self.lines[left], x))
+    }
+    /// min a*x + b, with amortized O(1); x should be >= any x queried before
+    pub fn query_monotone(&mut self, x: K) -> Option<K> {
+        while self.lines.len() >= 2 && Self::eval(self.lines[0], x) >= Self::eval(self.lines[1], x)
+        {
+            self.lines.pop_front();
+        }
+        self.lines.front().map(|&line| Self::eval(line, x))
+    }
+}
+impl<K: Ring + Copy + Ord> Default for MonotoneCHT<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test_convex_hull_trick {
+    use crate::num::random::xorshift::*;
+    use crate::opt::convex_hull_trick::*;
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for _ in 0..20 {
+            let mut lines: Vec<(i64, i64)> = (0..30)
+                .map(|_| (rand.gen::<i64>() % 20, rand.gen::<i64>() % 100))
+                .collect();
+            lines.sort_by(|x, y| y.cmp(x));
+            let mut cht = MonotoneCHT::new();
+            assert_eq!(cht.query(0), None);
+            for &(a, b) in lines.iter() {
+                cht.add_line(a, b);
+            }
+            for x in -30..30 {
+                let expected = lines.iter().map(|&(a, b)| a * x + b).min();
+                assert_eq!(cht.query(x), expected);
+                assert_eq!(cht.query_monotone(x), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ratio() {
+        use crate::algebra::ratio::*;
+        let mut cht = MonotoneCHT::new();
+        cht.add_line(Ratio::new(1, 2), Ratio::from(0));
+        cht.add_line(Ratio::new(-1, 3), Ratio::from(1));
+        // x/2 vs 1 - x/3, crossing at x = 6/5
+        assert_eq!(cht.query(Ratio::from(0)), Some(Ratio::from(0)));
+        assert_eq!(cht.query(Ratio::new(6, 5)), Some(Ratio::new(3, 5)));
+        assert_eq!(cht.query(Ratio::from(3)), Some(Ratio::from(0)));
+    }
+}
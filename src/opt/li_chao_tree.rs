@@ -0,0 +1,178 @@
+/// Optimization - Li Chao Tree
+use crate::algebra::ring::*;
+
+/// Min of lines (or segments) a*x + b, at x in the given coordinates.
+/// For max, add (-a, -b) and negate the results.
+#[derive(Debug, Clone)]
+pub struct LiChaoTree<K> {
+    xs: Vec<K>,
+    size: usize,
+    data: Vec<Option<(K, K)>>,
+}
+impl<K: Ring + Copy + Ord> LiChaoTree<K> {
+    /// Queries are only at xs
+    pub fn new(xs: &[K]) -> Self {
+        let mut xs = xs.to_vec();
+        xs.sort();
+        xs.dedup();
+        let size = xs.len().next_power_of_two();
+        // padding: the last coordinate is repeated
+        if let Some(&last) = xs.last() {
+            xs.resize(size, last);
+        }
+        Self {
+            xs,
+            size,
+            data: vec![None; 2 * size],
+        }
+    }
+    fn eval(line: (K, K), x: K) -> K {
+        line.0 * x + line.1
+    }
+    /// Add a*x + b on all x
+    pub fn add_line(&mut self, a: K, b: K) {
+        if self.xs.is_empty() {
+            return;
+        }
+        self.descend(1, 0, self.size, (a, b));
+    }
+    /// Add a*x + b only on x in range
+    pub fn add_segment<R: std::ops::RangeBounds<K>>(&mut self, a: K, b: K, range: R) {
+        use std::ops::Bound::*;
+        if self.xs.is_empty() {
+            return;
+        }
+        let left = match range.start_bound() {
+            Included(x) => self.xs.partition_point(|y| y < x),
+            Excluded(x) => self.xs.partition_point(|y| y <= x),
+            Unbounded => 0,
+        };
+        let right = match range.end_bound() {
+            Included(x) => self.xs.partition_point(|y| y <= x),
+            Excluded(x) => self.xs.partition_point(|y| y < x),
+            Unbounded => self.size,
+        };
+        let (mut l, mut r) = (left + self.size, right + self.size);
+        let mut width = 1;
+        while l < r {
+            if l & 1 == 1 {
+                let lo = (l - self.size / width) * width;
+                self.descend(l, lo, lo + width, (a, b));
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                let lo = (r - self.size / width) * width;
+                self.descend(r, lo, lo + width, (a, b));
+            }
+            l >>= 1;
+            r >>= 1;
+            width <<= 1;
+        }
+    }
+    /// Push the line into the node k, which covers xs[left..right]
+    fn descend(&mut self, k: usize, left: usize, right: usize, line: (K, K)) {
+        let mut line = line;
+        let (mut k, mut left, mut right) = (k, left, right);
+        loop {
+            let cur = match self.data[k] {
+                None => {
+                    self.data[k] = Some(line);
+                    return;
+                }
+                Some(cur) => cur,
+            };
+            let mid = (left + right) / 2;
+            let better_left = Self::eval(line, self.xs[left]) < Self::eval(cur, self.xs[left]);
+            let better_mid = Self::eval(line, self.xs[mid]) < Self::eval(cur, self.xs[mid]);
+            if better_mid {
+                self.data[k] = Some(line);
+                line = cur;
+            }
+            if right - left == 1 {
+                return;
+            }
+            if better_left != better_mid {
+                k *= 2;
+                right = mid;
+            } else {
+                k = 2 * k + 1;
+                left = mid;
+            }
+        }
+    }
+    /// min a*x + b at x, which should be one of the coordinates
+    pub fn query(&self, x: K) -> Option<K> {
+        let i = self.xs.partition_point(|y| y < &x);
+        assert!(
+            i < self.xs.len() && self.xs[i] == x,
+            "x is not a coordinate"
+        );
+        let mut k = i + self.size;
+        let mut result = None;
+        while k > 0 {
+            if let Some(line) = self.data[k] {
+                let y = Self::eval(line, x);
+                result = match result {
+                    Some(r) if r <= y => Some(r),
+                    _ => Some(y),
+                };
+            }
+            k >>= 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test_li_chao_tree {
+    use crate::num::random::xorshift::*;
+    use crate::opt::li_chao_tree::*;
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for _ in 0..20 {
+            let xs: Vec<i64> = (0..30).map(|_| rand.gen::<i64>() % 50).collect();
+            let mut tree = LiChaoTree::new(&xs);
+            let mut segments: Vec<(i64, i64, i64, i64)> = vec![];
+            for _ in 0..50 {
+                let (a, b) = (rand.gen::<i64>() % 20, rand.gen::<i64>() % 100);
+                if rand.gen::<usize>() % 3 == 2 {
+                    tree.add_line(a, b);
+                    segments.push((a, b, i64::MIN, i64::MAX));
+                } else {
+                    let l = rand.gen::<i64>() % 60;
+                    let r = rand.gen::<i64>() % 60;
+                    tree.add_segment(a, b, l..r);
+                    segments.push((a, b, l, r));
+                }
+                for &x in xs.iter() {
+                    let expected = segments
+                        .iter()
+                        .filter(|&&(_, _, l, r)| l <= x && x < r)
+                        .map(|&(a, b, _, _)| a * x + b)
+                        .min();
+                    assert_eq!(tree.query(x), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ratio() {
+        use crate::algebra::ratio::*;
+        let xs: Vec<Ratio> = (-4..=4).map(|i| Ratio::new(i, 2)).collect();
+        let mut tree = LiChaoTree::new(&xs);
+        assert_eq!(tree.query(Ratio::from(0)), None);
+        tree.add_line(Ratio::from(1), Ratio::from(0));
+        tree.add_segment(
+            Ratio::from(-1),
+            Ratio::from(0),
+            Ratio::from(0)..=Ratio::from(1),
+        );
+        assert_eq!(tree.query(Ratio::new(-1, 2)), Some(Ratio::new(-1, 2)));
+        assert_eq!(tree.query(Ratio::new(1, 2)), Some(Ratio::new(-1, 2)));
+        assert_eq!(tree.query(Ratio::new(3, 2)), Some(Ratio::new(3, 2)));
+    }
+}
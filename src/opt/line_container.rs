@@ -0,0 +1,159 @@
+/// Optimization - Line Container (fully dynamic Convex Hull Trick)
+use crate::algebra::ring::*;
+use crate::opt::convex_hull_trick::*;
+
+/// Fraction num/den (den > 0), compared without division
+#[derive(Debug, Clone, Copy)]
+struct Frac<K>(K, K);
+impl<K: Ring + Copy + Ord> PartialEq for Frac<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+impl<K: Ring + Copy + Ord> Eq for Frac<K> {}
+impl<K: Ring + Copy + Ord> PartialOrd for Frac<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<K: Ring + Copy + Ord> Ord for Frac<K> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.0 * other.1).cmp(&(other.0 * self.1))
+    }
+}
+
+/// Min of lines a*x + b, with O(log n) insertion in any order and O(log n) query.
+/// For max, add (-a, -b) and negate the results.
+#[derive(Debug, Clone)]
+pub struct LineContainer<K> {
+    lines: std::collections::BTreeMap<K, (K, Option<Frac<K>>)>, // a -> (b, x where the line starts to be min)
+    starts: std::collections::BTreeMap<Frac<K>, K>,             // x -> a
+}
+impl<K: Ring + Copy + Ord> LineContainer<K> {
+    pub fn new() -> Self {
+        Self {
+            lines: std::collections::BTreeMap::new(),
+            starts: std::collections::BTreeMap::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+    /// Line with the next larger slope
+    fn larger(&self, a: K) -> Option<(K, K)> {
+        use std::ops::Bound::*;
+        self.lines
+            .range((Excluded(a), Unbounded))
+            .next()
+            .map(|(&a, &(b, _))| (a, b))
+    }
+    /// Line with the next smaller slope
+    fn smaller(&self, a: K) -> Option<(K, K)> {
+        self.lines
+            .range(..a)
+            .next_back()
+            .map(|(&a, &(b, _))| (a, b))
+    }
+    fn remove(&mut self, a: K) {
+        if let Some((_, Some(start))) = self.lines.remove(&a) {
+            self.starts.remove(&start);
+        }
+    }
+    /// Recompute where the line a starts to be min
+    fn refresh(&mut self, a: K) {
+        let b = self.lines[&a].0;
+        if let Some(start) = self.lines[&a].1 {
+            self.starts.remove(&start);
+        }
+        let start = self.larger(a).map(|(a1, b1)| Frac(b - b1, a1 - a));
+        if let Some(start) = start {
+            self.starts.insert(start, a);
+        }
+        self.lines.insert(a, (b, start));
+    }
+    /// Add a*x + b
+    pub fn add_line(&mut self, a: K, b: K) {
+        if let Some(&(b0, _)) = self.lines.get(&a) {
+            if b0 <= b {
+                return;
+            }
+            self.remove(a);
+        }
+        if let (Some(l1), Some(l3)) = (self.larger(a), self.smaller(a)) {
+            if useless_line(l1, (a, b), l3) {
+                return;
+            }
+        }
+        self.lines.insert(a, (b, None));
+        while let Some(l2) = self.smaller(a) {
+            match self.smaller(l2.0) {
+                Some(l3) if useless_line((a, b), l2, l3) => self.remove(l2.0),
+                _ => break,
+            }
+        }
+        while let Some(l2) = self.larger(a) {
+            match self.larger(l2.0) {
+                Some(l1) if useless_line(l1, l2, (a, b)) => self.remove(l2.0),
+                _ => break,
+            }
+        }
+        if let Some((a3, _)) = self.smaller(a) {
+            self.refresh(a3);
+        }
+        self.refresh(a);
+    }
+    /// min a*x + b
+    pub fn query(&self, x: K) -> Option<K> {
+        let a = match self.starts.range(..=Frac(x, K::one())).next_back() {
+            Some((_, &a)) => a,
+            None => *self.lines.keys().next_back()?,
+        };
+        Some(a * x + self.lines[&a].0)
+    }
+}
+impl<K: Ring + Copy + Ord> Default for LineContainer<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test_line_container {
+    use crate::num::random::xorshift::*;
+    use crate::opt::line_container::*;
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for _ in 0..20 {
+            let mut lc = LineContainer::new();
+            let mut lines: Vec<(i64, i64)> = vec![];
+            assert_eq!(lc.query(0), None);
+            for _ in 0..50 {
+                let (a, b) = (rand.gen::<i64>() % 20, rand.gen::<i64>() % 100);
+                lc.add_line(a, b);
+                lines.push((a, b));
+                for x in -30..30 {
+                    let expected = lines.iter().map(|&(a, b)| a * x + b).min();
+                    assert_eq!(lc.query(x), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ratio() {
+        use crate::algebra::ratio::*;
+        let mut lc = LineContainer::new();
+        lc.add_line(Ratio::from(0), Ratio::from(1));
+        lc.add_line(Ratio::new(1, 2), Ratio::from(0));
+        lc.add_line(Ratio::new(-1, 2), Ratio::from(0));
+        assert_eq!(lc.query(Ratio::from(0)), Some(Ratio::from(0)));
+        assert_eq!(lc.query(Ratio::new(-3, 2)), Some(Ratio::new(-3, 4)));
+        assert_eq!(lc.query(Ratio::new(1, 3)), Some(Ratio::new(-1, 6)));
+        assert_eq!(lc.len(), 2);
+    }
+}
@@ -1,3 +1,6 @@
+pub mod convex_hull_trick;
+pub mod li_chao_tree;
+pub mod line_container;
 pub mod three_sat;
 pub mod two_sat;
 pub mod umeru_moyasu;
@@ -3,4 +3,7 @@ pub mod cumsum2d;
 pub mod lis;
 pub mod median_heap;
 pub mod slide_min;
+pub mod sqrt_buckets;
+pub mod succinct_bit_vector;
+pub mod swag;
 pub mod tree;
@@ -0,0 +1,265 @@
+/// Sequence - Square Root Decomposition (Buckets with lazy acts)
+use crate::algebra::act::*;
+use crate::algebra::monoid::*;
+
+/// Per-bucket summary; override this for acts which don't act on the product
+pub trait Bucket<X, M>: Sized {
+    /// Rebuild the summary from the items
+    fn build(xs: &[X]) -> Self;
+    /// Product of the items
+    fn product(&self) -> X;
+    /// Act on all the items, or false if impossible without looking at each item
+    /// (then, the bucket is rebuilt from the acted items)
+    fn act(&mut self, m: M) -> bool;
+}
+
+/// Default summary, holding only the product
+#[derive(Debug, Clone, Copy)]
+pub struct ProductBucket<X>(pub X);
+impl<X: Copy + Monoid, M: Act<X>> Bucket<X, M> for ProductBucket<X> {
+    fn build(xs: &[X]) -> Self {
+        ProductBucket(xs.iter().cloned().product())
+    }
+    fn product(&self) -> X {
+        self.0
+    }
+    fn act(&mut self, m: M) -> bool {
+        self.0 = m.act(self.0);
+        true
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SqrtBuckets<X, M, B = ProductBucket<X>> {
+    length: usize,
+    size: usize,         // of a bucket
+    items: Vec<X>,       // without the pending acts
+    buckets: Vec<B>,     // summaries, with the pending acts
+    act: Vec<Option<M>>, // pending acts for items; None is identity
+}
+impl<X: Copy + Monoid, M: Copy + Monoid + Act<X>, B: Bucket<X, M>> SqrtBuckets<X, M, B> {
+    pub fn new(length: usize, bucket_size: usize) -> Self {
+        Self::from(vec![X::one(); length], bucket_size)
+    }
+    pub fn from(xs: Vec<X>, bucket_size: usize) -> Self {
+        assert!(bucket_size > 0);
+        let length = xs.len();
+        let buckets = xs.chunks(bucket_size).map(B::build).collect();
+        #[allow(clippy::manual_div_ceil)] // div_ceil needs Rust 1.73
+        let act = vec![None; (length + bucket_size - 1) / bucket_size];
+        Self {
+            length,
+            size: bucket_size,
+            items: xs,
+            buckets,
+            act,
+        }
+    }
+    fn bounds<R: std::ops::RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        use std::ops::Bound::*;
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r + 1,
+            Excluded(&r) => r,
+            Unbounded => self.length,
+        };
+        assert!(r <= self.length);
+        (l, r)
+    }
+    /// Items in the bucket b
+    fn span(&self, b: usize) -> std::ops::Range<usize> {
+        b * self.size..std::cmp::min((b + 1) * self.size, self.length)
+    }
+    /// Apply the pending act to the items
+    fn push(&mut self, b: usize) {
+        if let Some(m) = self.act[b].take() {
+            for i in self.span(b) {
+                self.items[i] = m.act(self.items[i]);
+            }
+        }
+    }
+    fn rebuild(&mut self, b: usize) {
+        self.buckets[b] = B::build(&self.items[self.span(b)]);
+    }
+    pub fn get(&self, i: usize) -> X {
+        assert!(i < self.length);
+        match self.act[i / self.size] {
+            Some(m) => m.act(self.items[i]),
+            None => self.items[i],
+        }
+    }
+    pub fn set(&mut self, i: usize, x: X) {
+        assert!(i < self.length);
+        let b = i / self.size;
+        self.push(b);
+        self.items[i] = x;
+        self.rebuild(b);
+    }
+    /// Act m on items in range
+    pub fn update<R: std::ops::RangeBounds<usize>>(&mut self, range: R, m: M) {
+        let (l, r) = self.bounds(range);
+        if l >= r {
+            return;
+        }
+        for b in l / self.size..=(r - 1) / self.size {
+            let span = self.span(b);
+            if l <= span.start && span.end <= r && self.buckets[b].act(m) {
+                self.act[b] = Some(compose(self.act[b], m));
+                continue;
+            }
+            self.push(b);
+            for i in std::cmp::max(l, span.start)..std::cmp::min(r, span.end) {
+                self.items[i] = m.act(self.items[i]);
+            }
+            self.rebuild(b);
+        }
+    }
+    pub fn product<R: std::ops::RangeBounds<usize>>(&self, range: R) -> X {
+        let (l, r) = self.bounds(range);
+        let mut result = X::one();
+        if l >= r {
+            return result;
+        }
+        for b in l / self.size..=(r - 1) / self.size {
+            let span = self.span(b);
+            if l <= span.start && span.end <= r {
+                result = result * self.buckets[b].product();
+            } else {
+                for i in std::cmp::max(l, span.start)..std::cmp::min(r, span.end) {
+                    result = result * self.get(i);
+                }
+            }
+        }
+        result
+    }
+    pub fn to_vec(&self) -> Vec<X> {
+        (0..self.length).map(|i| self.get(i)).collect()
+    }
+}
+/// m then n
+fn compose<M: Monoid>(m: Option<M>, n: M) -> M {
+    match m {
+        Some(m) => m * n,
+        None => n,
+    }
+}
+
+#[cfg(test)]
+mod test_sqrt_buckets {
+    use crate::algebra::act_add::*;
+    use crate::algebra::monoid_max::*;
+    use crate::monoid; // IGNORE
+    use crate::num::random::xorshift::*;
+    use crate::sequence::sqrt_buckets::*;
+
+    #[test]
+    fn test_random_ops() {
+        let mut rand = XorShift::new();
+        for n in 1..20 {
+            for bucket_size in 1..6 {
+                let mut xs: Vec<i64> = (0..n).map(|_| rand.gen::<i64>() % 100).collect();
+                let mut sb: SqrtBuckets<MaxInt<i64>, Add<MaxInt<i64>>> =
+                    SqrtBuckets::from(xs.iter().map(|&x| MaxInt::Val(x)).collect(), bucket_size);
+                for _ in 0..50 {
+                    let l = rand.gen::<usize>() % (n + 1);
+                    let r = l + rand.gen::<usize>() % (n + 1 - l);
+                    match rand.gen::<usize>() % 3 {
+                        0 => {
+                            let x = rand.gen::<i64>() % 10;
+                            sb.update(l..r, Add(MaxInt::Val(x)));
+                            for y in xs[l..r].iter_mut() {
+                                *y += x;
+                            }
+                        }
+                        1 if l < n => {
+                            let x = rand.gen::<i64>() % 100;
+                            sb.set(l, MaxInt::Val(x));
+                            xs[l] = x;
+                        }
+                        _ => {}
+                    }
+                    assert_eq!(
+                        sb.product(l..r),
+                        xs[l..r].iter().map(|&x| MaxInt::Val(x)).product()
+                    );
+                    assert_eq!(
+                        sb.to_vec(),
+                        xs.iter().map(|&x| MaxInt::Val(x)).collect::<Vec<_>>()
+                    );
+                }
+            }
+        }
+    }
+
+    /// Sum; with range chmin, which can't act on the sum itself
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Sum(i64);
+    monoid! {
+        Sum;
+        one = Sum(0);
+        mul(self, other) = { Sum(std::ops::Add::add(self.0, other.0)) };
+    }
+    #[derive(Debug, Clone, Copy)]
+    struct Chmin(i64);
+    monoid! {
+        Chmin;
+        one = Chmin(i64::MAX);
+        mul(self, other) = { Chmin(self.0.min(other.0)) };
+    }
+    impl Act<Sum> for Chmin {
+        fn act(&self, x: Sum) -> Sum {
+            Sum(x.0.min(self.0))
+        }
+    }
+    /// (sum, max, count of max, second max)
+    struct ChminBucket(i64, i64, i64, i64);
+    impl Bucket<Sum, Chmin> for ChminBucket {
+        fn build(xs: &[Sum]) -> Self {
+            let sum = xs.iter().map(|x| x.0).sum();
+            let max = xs.iter().map(|x| x.0).max().unwrap();
+            let count = xs.iter().filter(|x| x.0 == max).count() as i64;
+            let second = xs.iter().map(|x| x.0).filter(|&x| x < max).max();
+            ChminBucket(sum, max, count, second.unwrap_or(i64::MIN))
+        }
+        fn product(&self) -> Sum {
+            Sum(self.0)
+        }
+        fn act(&mut self, m: Chmin) -> bool {
+            if m.0 >= self.1 {
+                true
+            } else if m.0 > self.3 {
+                self.0 -= (self.1 - m.0) * self.2;
+                self.1 = m.0;
+                true
+            } else {
+                false
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_bucket() {
+        let mut rand = XorShift::new();
+        let n = 100;
+        let mut xs: Vec<i64> = (0..n).map(|_| rand.gen::<i64>() % 1000).collect();
+        let mut sb: SqrtBuckets<Sum, Chmin, ChminBucket> =
+            SqrtBuckets::from(xs.iter().map(|&x| Sum(x)).collect(), 10);
+        for _ in 0..300 {
+            let l = rand.gen::<usize>() % (n + 1);
+            let r = l + rand.gen::<usize>() % (n + 1 - l);
+            let x = rand.gen::<i64>() % 1000;
+            sb.update(l..r, Chmin(x));
+            for y in xs[l..r].iter_mut() {
+                *y = std::cmp::min(*y, x);
+            }
+            let l = rand.gen::<usize>() % (n + 1);
+            let r = l + rand.gen::<usize>() % (n + 1 - l);
+            assert_eq!(sb.product(l..r), Sum(xs[l..r].iter().sum()));
+            assert_eq!(sb.product(..), Sum(xs.iter().sum()));
+        }
+    }
+}
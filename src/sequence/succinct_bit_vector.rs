@@ -0,0 +1,120 @@
+/// Sequence - Succinct Bit Vector (rank/select)
+#[derive(Debug, Clone)]
+pub struct SuccinctBitVector {
+    length: usize,
+    words: Vec<u64>,
+    ranks: Vec<usize>, // ranks[b] is the number of 1s in words[..b]
+}
+impl SuccinctBitVector {
+    pub fn from(bits: &[bool]) -> Self {
+        let length = bits.len();
+        let mut words = vec![0_u64; length / 64 + 1];
+        for (i, &b) in bits.iter().enumerate() {
+            if b {
+                words[i / 64] |= 1 << (i % 64);
+            }
+        }
+        let mut ranks = vec![0; words.len() + 1];
+        for (b, w) in words.iter().enumerate() {
+            ranks[b + 1] = ranks[b] + w.count_ones() as usize;
+        }
+        Self {
+            length,
+            words,
+            ranks,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.length
+    }
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+    /// self[i]
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.length);
+        (self.words[i / 64] >> (i % 64)) & 1 == 1
+    }
+    /// Number of 1s in [0, i)
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.length);
+        let mask = (1_u64 << (i % 64)).wrapping_sub(1);
+        self.ranks[i / 64] + (self.words[i / 64] & mask).count_ones() as usize
+    }
+    /// Number of 0s in [0, i)
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+    /// Position of the k-th (0-indexed) 1
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        if k >= self.ranks[self.words.len()] {
+            return None;
+        }
+        // max b s.t. ranks[b] <= k
+        let (mut left, mut right) = (0, self.words.len());
+        while right - left > 1 {
+            let mid = (left + right) / 2;
+            if self.ranks[mid] <= k {
+                left = mid;
+            } else {
+                right = mid;
+            }
+        }
+        Some(left * 64 + select_in_word(self.words[left], k - self.ranks[left]))
+    }
+    /// Position of the k-th (0-indexed) 0
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        if k >= self.rank0(self.length) {
+            return None;
+        }
+        let zeros = |b: usize| b * 64 - self.ranks[b];
+        let (mut left, mut right) = (0, self.words.len());
+        while right - left > 1 {
+            let mid = (left + right) / 2;
+            if zeros(mid) <= k {
+                left = mid;
+            } else {
+                right = mid;
+            }
+        }
+        Some(left * 64 + select_in_word(!self.words[left], k - zeros(left)))
+    }
+}
+
+/// Position of the k-th (0-indexed) 1 in w
+fn select_in_word(w: u64, k: usize) -> usize {
+    let mut w = w;
+    for _ in 0..k {
+        w &= w - 1;
+    }
+    w.trailing_zeros() as usize
+}
+
+#[cfg(test)]
+mod test_succinct_bit_vector {
+    use crate::num::random::xorshift::*;
+    use crate::sequence::succinct_bit_vector::*;
+
+    #[test]
+    fn test_rank_select() {
+        let mut rand = XorShift::new();
+        for &n in &[0, 1, 63, 64, 65, 200, 1000] {
+            let bits: Vec<bool> = (0..n).map(|_| rand.gen::<u64>() & 3 == 0).collect();
+            let bv = SuccinctBitVector::from(&bits);
+            assert_eq!(bv.len(), n);
+            let ones: Vec<usize> = (0..n).filter(|&i| bits[i]).collect();
+            let zeros: Vec<usize> = (0..n).filter(|&i| !bits[i]).collect();
+            for i in 0..=n {
+                assert_eq!(bv.rank1(i), bits[..i].iter().filter(|&&b| b).count());
+                assert_eq!(bv.rank0(i), i - bv.rank1(i));
+            }
+            for (i, &b) in bits.iter().enumerate() {
+                assert_eq!(bv.get(i), b);
+            }
+            for k in 0..=n {
+                assert_eq!(bv.select1(k), ones.get(k).cloned());
+                assert_eq!(bv.select0(k), zeros.get(k).cloned());
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+/// Sequence - SWAG (Sliding Window Aggregation); Foldable Queue/Deque of Monoid
+use crate::algebra::monoid::*;
+
+/// Queue with amortized O(1) fold (the product from front to back)
+#[derive(Debug, Clone)]
+pub struct FoldableQueue<X> {
+    front: Vec<(X, X)>, // (x, product from x to the bottom); the top is the front
+    back: Vec<X>,
+    back_fold: X,
+}
+impl<X: Copy + Monoid> FoldableQueue<X> {
+    pub fn new() -> Self {
+        Self {
+            front: vec![],
+            back: vec![],
+            back_fold: X::one(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn push_back(&mut self, x: X) {
+        self.back.push(x);
+        self.back_fold = self.back_fold * x;
+    }
+    pub fn pop_front(&mut self) -> Option<X> {
+        if self.front.is_empty() {
+            while let Some(x) = self.back.pop() {
+                let fold = match self.front.last() {
+                    Some(&(_, f)) => x * f,
+                    None => x,
+                };
+                self.front.push((x, fold));
+            }
+            self.back_fold = X::one();
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+    pub fn fold(&self) -> X {
+        match self.front.last() {
+            Some(&(_, f)) => f * self.back_fold,
+            None => self.back_fold,
+        }
+    }
+}
+impl<X: Copy + Monoid> Default for FoldableQueue<X> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Deque with amortized O(1) fold (the product from front to back)
+#[derive(Debug, Clone)]
+pub struct FoldableDeque<X> {
+    front: Vec<(X, X)>, // (x, product from x to the bottom); the top is the front
+    back: Vec<(X, X)>,  // (x, product from the bottom to x); the top is the back
+}
+impl<X: Copy + Monoid> FoldableDeque<X> {
+    pub fn new() -> Self {
+        Self {
+            front: vec![],
+            back: vec![],
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn push_front(&mut self, x: X) {
+        let fold = match self.front.last() {
+            Some(&(_, f)) => x * f,
+            None => x,
+        };
+        self.front.push((x, fold));
+    }
+    pub fn push_back(&mut self, x: X) {
+        let fold = match self.back.last() {
+            Some(&(_, f)) => f * x,
+            None => x,
+        };
+        self.back.push((x, fold));
+    }
+    /// Rebuild stacks from xs (in order), whose first `mid` items go to the front
+    fn rebuild(&mut self, xs: Vec<X>, mid: usize) {
+        self.front.clear();
+        self.back.clear();
+        for &x in xs[..mid].iter().rev() {
+            self.push_front(x);
+        }
+        for &x in xs[mid..].iter() {
+            self.push_back(x);
+        }
+    }
+    pub fn pop_front(&mut self) -> Option<X> {
+        if self.front.is_empty() {
+            let xs: Vec<X> = self.back.iter().map(|&(x, _)| x).collect();
+            #[allow(clippy::manual_div_ceil)] // div_ceil needs Rust 1.73
+            let mid = (xs.len() + 1) / 2;
+            self.rebuild(xs, mid);
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+    pub fn pop_back(&mut self) -> Option<X> {
+        if self.back.is_empty() {
+            let xs: Vec<X> = self.front.iter().rev().map(|&(x, _)| x).collect();
+            let mid = xs.len() / 2;
+            self.rebuild(xs, mid);
+        }
+        self.back.pop().map(|(x, _)| x)
+    }
+    pub fn fold(&self) -> X {
+        match (self.front.last(), self.back.last()) {
+            (Some(&(_, f)), Some(&(_, g))) => f * g,
+            (Some(&(_, f)), None) => f,
+            (None, Some(&(_, g))) => g,
+            (None, None) => X::one(),
+        }
+    }
+}
+impl<X: Copy + Monoid> Default for FoldableDeque<X> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test_swag {
+    use crate::monoid; // IGNORE
+    use crate::num::random::xorshift::*;
+    use crate::sequence::swag::*;
+
+    /// x -> ax + b (mod 998244353); f * g is "f then g"
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Affine(u64, u64);
+    const MOD: u64 = 998_244_353;
+    monoid! {
+        Affine;
+        one = Affine(1, 0);
+        mul(self, other) = {
+            Affine(self.0 * other.0 % MOD, (self.1 * other.0 + other.1) % MOD)
+        };
+    }
+
+    #[test]
+    fn test_queue() {
+        let mut rand = XorShift::new();
+        let mut q = FoldableQueue::new();
+        let mut xs = std::collections::VecDeque::new();
+        for _ in 0..1000 {
+            if rand.gen::<usize>() % 3 == 2 {
+                assert_eq!(q.pop_front(), xs.pop_front());
+            } else {
+                let x = Affine(rand.gen::<u64>() % MOD, rand.gen::<u64>() % MOD);
+                q.push_back(x);
+                xs.push_back(x);
+            }
+            assert_eq!(q.len(), xs.len());
+            assert_eq!(q.fold(), xs.iter().cloned().product());
+        }
+    }
+
+    #[test]
+    fn test_deque() {
+        let mut rand = XorShift::new();
+        let mut q = FoldableDeque::new();
+        let mut xs = std::collections::VecDeque::new();
+        for _ in 0..1000 {
+            let x = Affine(rand.gen::<u64>() % MOD, rand.gen::<u64>() % MOD);
+            match rand.gen::<usize>() % 4 {
+                0 => {
+                    q.push_front(x);
+                    xs.push_front(x);
+                }
+                1 => {
+                    q.push_back(x);
+                    xs.push_back(x);
+                }
+                2 => assert_eq!(q.pop_front(), xs.pop_front()),
+                _ => assert_eq!(q.pop_back(), xs.pop_back()),
+            }
+            assert_eq!(q.len(), xs.len());
+            assert_eq!(q.fold(), xs.iter().cloned().product());
+        }
+    }
+}
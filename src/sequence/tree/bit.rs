@@ -46,6 +46,48 @@ impl<X: Copy + AGroup> BIT<X> {
         }
         self.sum_up(range.end) - self.sum_up(range.start)
     }
+    /// Max r (>= l) s.t. pred(sum(l..r)), with O(log n)
+    /// pred(sum_up(r) - sum_up(l)) should be monotone on r in [0, n] (e.g. non-negative weights)
+    pub fn max_right<F: Fn(X) -> bool>(&self, l: usize, pred: F) -> usize {
+        let base = self.sum_up(l);
+        let mut pos = 0;
+        let mut acc = X::zero();
+        let mut k = self.size.next_power_of_two();
+        while k > 0 {
+            if pos + k <= self.size && pred(acc + self.array[pos + k] - base) {
+                pos += k;
+                acc = acc + self.array[pos];
+            }
+            k >>= 1;
+        }
+        std::cmp::max(pos, l)
+    }
+    /// Min l (<= r) s.t. pred(sum(l..r)), with O(log n)
+    /// pred(sum_up(r) - sum_up(l)) should be monotone on l in [0, n] (e.g. non-negative weights)
+    pub fn min_left<F: Fn(X) -> bool>(&self, r: usize, pred: F) -> usize {
+        let base = self.sum_up(r);
+        if pred(base) {
+            return 0;
+        }
+        // max pos s.t. !pred(sum(pos..r))
+        let mut pos = 0;
+        let mut acc = X::zero();
+        let mut k = self.size.next_power_of_two();
+        while k > 0 {
+            if pos + k <= self.size && !pred(base - (acc + self.array[pos + k])) {
+                pos += k;
+                acc = acc + self.array[pos];
+            }
+            k >>= 1;
+        }
+        std::cmp::min(pos + 1, r)
+    }
+}
+impl<X: Copy + AGroup + PartialOrd> BIT<X> {
+    /// Min i s.t. sum_up(i + 1) >= w (or size if no such i), for non-negative weights
+    pub fn lower_bound(&self, w: X) -> usize {
+        self.max_right(0, |s| s < w)
+    }
 }
 
 #[cfg(test)]
@@ -64,4 +106,35 @@ mod test_bit {
             ac = ac + i as i64;
         }
     }
+
+    #[test]
+    fn test_max_right_min_left() {
+        let xs: Vec<i64> = vec![3, 1, 4, 1, 5, 9, 2, 6, 5];
+        let bit = BIT::from(&xs);
+        for k in 0..40 {
+            for l in 0..=xs.len() {
+                let mut r = l;
+                while r < xs.len() && xs[l..=r].iter().sum::<i64>() <= k {
+                    r += 1;
+                }
+                assert_eq!(bit.max_right(l, |s| s <= k), r);
+            }
+            for r in 0..=xs.len() {
+                let mut l = r;
+                while l > 0 && xs[l - 1..r].iter().sum::<i64>() <= k {
+                    l -= 1;
+                }
+                assert_eq!(bit.min_left(r, |s| s <= k), l);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lower_bound() {
+        let bit = BIT::from(&vec![3_i64, 0, 4, 1, 0, 9]);
+        let expected = [0, 0, 0, 0, 2, 2, 2, 2, 3, 5, 5, 5, 5, 5, 5, 5, 5, 5, 6];
+        for (w, &i) in expected.iter().enumerate() {
+            assert_eq!(bit.lower_bound(w as i64), i);
+        }
+    }
 }
@@ -0,0 +1,91 @@
+/// Sequence - 2D Binary Indexed Tree (Fenwick Tree) of Additive Group (+, 0)
+use crate::algebra::group_additive::*;
+
+pub struct BIT2D<X> {
+    height: usize,
+    width: usize,
+    array: Vec<Vec<X>>,
+}
+impl<X: Copy + AGroup> BIT2D<X> {
+    pub fn new(height: usize, width: usize) -> Self {
+        BIT2D {
+            height,
+            width,
+            array: vec![vec![X::zero(); width + 1]; height + 1],
+        }
+    }
+    pub fn from(xs: &[Vec<X>]) -> Self {
+        let height = xs.len();
+        let width = if height > 0 { xs[0].len() } else { 0 };
+        let mut r = Self::new(height, width);
+        for (i, row) in xs.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                r.add(i, j, x);
+            }
+        }
+        r
+    }
+    pub fn add(&mut self, i: usize, j: usize, w: X) {
+        let mut x = i + 1;
+        while x <= self.height {
+            let mut y = j + 1;
+            while y <= self.width {
+                self.array[x][y] = self.array[x][y] + w;
+                y += y & y.wrapping_neg();
+            }
+            x += x & x.wrapping_neg();
+        }
+    }
+    /// sum of [0, i) x [0, j)
+    pub fn sum_up(&self, i: usize, j: usize) -> X {
+        let mut sum = X::zero();
+        let mut x = i;
+        while x > 0 {
+            let mut y = j;
+            while y > 0 {
+                sum = sum + self.array[x][y];
+                y -= y & y.wrapping_neg();
+            }
+            x -= x & x.wrapping_neg();
+        }
+        sum
+    }
+    /// sum of the rectangle [top, bottom) x [left, right)
+    pub fn sum(&self, is: std::ops::Range<usize>, js: std::ops::Range<usize>) -> X {
+        if is.end <= is.start || js.end <= js.start {
+            return X::zero();
+        }
+        self.sum_up(is.end, js.end) - self.sum_up(is.start, js.end) - self.sum_up(is.end, js.start)
+            + self.sum_up(is.start, js.start)
+    }
+}
+
+#[cfg(test)]
+mod test_bit_2d {
+    use crate::num::random::xorshift::*;
+    use crate::sequence::tree::bit_2d::*;
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        let (h, w) = (7, 9);
+        let mut xs = vec![vec![0_i64; w]; h];
+        let mut bit = BIT2D::new(h, w);
+        for _ in 0..200 {
+            let (i, j) = (rand.gen::<usize>() % h, rand.gen::<usize>() % w);
+            let x = rand.gen::<i64>() % 100;
+            bit.add(i, j, x);
+            xs[i][j] += x;
+            let top = rand.gen::<usize>() % (h + 1);
+            let bottom = top + rand.gen::<usize>() % (h + 1 - top);
+            let left = rand.gen::<usize>() % (w + 1);
+            let right = left + rand.gen::<usize>() % (w + 1 - left);
+            let expected: i64 = xs[top..bottom]
+                .iter()
+                .map(|row| row[left..right].iter().sum::<i64>())
+                .sum();
+            assert_eq!(bit.sum(top..bottom, left..right), expected);
+        }
+        assert_eq!(BIT2D::from(&xs).sum(0..h, 0..w), bit.sum(0..h, 0..w));
+    }
+}
@@ -0,0 +1,95 @@
+/// Sequence - Range-Add Range-Sum BIT (Fenwick Tree) of Additive Group (+, 0)
+use crate::algebra::group_additive::*;
+use crate::sequence::tree::bit::*;
+
+/// sum_up(i) = b0.sum_up(i) + b1.sum_up(i) * i
+pub struct RangeBIT<X> {
+    size: usize,
+    b0: BIT<X>,
+    b1: BIT<X>,
+}
+impl<X: Copy + AGroup> RangeBIT<X> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            b0: BIT::new(size),
+            b1: BIT::new(size),
+        }
+    }
+    pub fn from(xs: &Vec<X>) -> Self {
+        Self {
+            size: xs.len(),
+            b0: BIT::from(xs),
+            b1: BIT::new(xs.len()),
+        }
+    }
+    /// Add x to each of [left, right)
+    pub fn add(&mut self, range: std::ops::Range<usize>, x: X) {
+        if range.end <= range.start {
+            return;
+        }
+        assert!(range.end <= self.size);
+        self.b0.add(range.start, -times(x, range.start));
+        self.b0.add(range.end, times(x, range.end));
+        self.b1.add(range.start, x);
+        self.b1.add(range.end, -x);
+    }
+    /// sum of [0, idx)
+    pub fn sum_up(&self, idx: usize) -> X {
+        self.b0.sum_up(idx) + times(self.b1.sum_up(idx), idx)
+    }
+    /// sum of [left, right)
+    pub fn sum(&self, range: std::ops::Range<usize>) -> X {
+        if range.end <= range.start {
+            return X::zero();
+        }
+        self.sum_up(range.end) - self.sum_up(range.start)
+    }
+    pub fn at(&self, idx: usize) -> X {
+        self.sum(idx..idx + 1)
+    }
+}
+
+/// x + x + .. + x (n times), with O(log n)
+fn times<X: Copy + AGroup>(x: X, n: usize) -> X {
+    let mut r = X::zero();
+    let mut x = x;
+    let mut n = n;
+    while n > 0 {
+        if n & 1 == 1 {
+            r = r + x;
+        }
+        x = x + x;
+        n >>= 1;
+    }
+    r
+}
+
+#[cfg(test)]
+mod test_bit_range {
+    use crate::num::random::xorshift::*;
+    use crate::sequence::tree::bit_range::*;
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        let n = 20;
+        let mut xs: Vec<i64> = (0..n).map(|_| rand.gen::<i64>() % 100).collect();
+        let mut bit = RangeBIT::from(&xs);
+        for _ in 0..200 {
+            let l = rand.gen::<usize>() % (n + 1);
+            let r = l + rand.gen::<usize>() % (n + 1 - l);
+            let x = rand.gen::<i64>() % 100;
+            bit.add(l..r, x);
+            for y in xs[l..r].iter_mut() {
+                *y += x;
+            }
+            let l = rand.gen::<usize>() % (n + 1);
+            let r = l + rand.gen::<usize>() % (n + 1 - l);
+            assert_eq!(bit.sum(l..r), xs[l..r].iter().sum::<i64>());
+            if l < n {
+                assert_eq!(bit.at(l), xs[l]);
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+/// Sequence - Disjoint Sparse Table
+use crate::algebra::monoid::*;
+
+/// O(1) range product for any Monoid (not needed to be idempotent nor commutative)
+#[derive(Debug)]
+pub struct DisjointSparseTable<X: Monoid> {
+    data: Vec<Vec<X>>, // data[k] has products from/to the centers of blocks of size 2**(k+1)
+}
+impl<X: Monoid + Copy> DisjointSparseTable<X> {
+    pub fn new(xs: &[X]) -> Self {
+        let n = xs.len();
+        let mut log = 1;
+        while (1 << log) < n {
+            log += 1;
+        }
+        let mut data = vec![xs.to_vec(); log];
+        for (k, row) in data.iter_mut().enumerate().skip(1) {
+            let half = 1 << k;
+            for mid in (half..n).step_by(2 * half) {
+                for i in (mid - half..mid - 1).rev() {
+                    row[i] = xs[i] * row[i + 1];
+                }
+                for i in mid + 1..std::cmp::min(mid + half, n) {
+                    row[i] = row[i - 1] * xs[i];
+                }
+            }
+        }
+        Self { data }
+    }
+    pub fn product(&self, range: std::ops::Range<usize>) -> X {
+        if range.start >= range.end {
+            return X::one();
+        }
+        let (l, r) = (range.start, range.end - 1);
+        if l == r {
+            return self.data[0][l];
+        }
+        let k = (usize::BITS - 1 - (l ^ r).leading_zeros()) as usize;
+        self.data[k][l] * self.data[k][r]
+    }
+}
+
+#[cfg(test)]
+mod test_disjoint_sparse_table {
+    use crate::algebra::act::*;
+    use crate::algebra::act_assign::*;
+    use crate::algebra::monoid_sum::*;
+    use crate::num::random::xorshift::*;
+    use crate::sequence::tree::disjoint_sparse_table::*;
+
+    #[test]
+    fn test_sum() {
+        let mut rand = XorShift::new();
+        for n in 0..40 {
+            let xs: Vec<Sum> = (0..n).map(|_| Sum(rand.gen::<i128>() % 100)).collect();
+            let st = DisjointSparseTable::new(&xs);
+            for l in 0..=n {
+                for r in l..=n {
+                    assert_eq!(st.product(l..r), xs[l..r].iter().cloned().product());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_commutative() {
+        // product is the last Some
+        let xs = [
+            Assign::Some(1),
+            Assign::None,
+            Assign::Some(3),
+            Assign::None,
+            Assign::None,
+            Assign::Some(6),
+            Assign::None,
+        ];
+        let st = DisjointSparseTable::new(&xs);
+        for l in 0..=xs.len() {
+            for r in l..=xs.len() {
+                let last = (l..r)
+                    .rev()
+                    .find_map(|i| match xs[i] {
+                        Assign::Some(x) => Some(x),
+                        Assign::None => None,
+                    })
+                    .unwrap_or(0);
+                assert_eq!(st.product(l..r).act(0), last);
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+/// Sequence - Dynamic Segment Tree (sparse, on i64 indices)
+use crate::algebra::act::*;
+use crate::algebra::monoid::*;
+
+/// Point update & range product on [lo, hi) of i64.
+/// Nodes are made only on touched paths (O(log(hi - lo)) nodes for each update).
+#[derive(Debug, Clone)]
+pub struct DynamicSegmentTree<X> {
+    lo: i64,
+    hi: i64,
+    log: usize,                // width of root == 2**log
+    unit: Vec<X>,              // unit[d] is the product of an untouched node at depth d
+    data: Vec<X>,              // data[0] is the root
+    children: Vec<[usize; 2]>, // NONE is none
+}
+impl<X: Copy + Monoid> DynamicSegmentTree<X> {
+    pub fn new(range: std::ops::Range<i64>) -> Self {
+        Self::with_default(range, X::one())
+    }
+    /// Every item is initially x
+    pub fn with_default(range: std::ops::Range<i64>, x: X) -> Self {
+        let (log, unit) = units(&range, x);
+        let mut tree = Self {
+            lo: range.start,
+            hi: range.end,
+            log,
+            unit,
+            data: vec![],
+            children: vec![],
+        };
+        tree.node(0);
+        tree
+    }
+    fn node(&mut self, depth: usize) -> usize {
+        self.data.push(self.unit[depth]);
+        self.children.push([NONE, NONE]);
+        self.data.len() - 1
+    }
+    fn child(&mut self, u: usize, side: usize, depth: usize) -> usize {
+        if self.children[u][side] == NONE {
+            let v = self.node(depth + 1);
+            self.children[u][side] = v;
+        }
+        self.children[u][side]
+    }
+    /// Number of nodes made
+    pub fn nodes(&self) -> usize {
+        self.data.len()
+    }
+    fn update_sub(&mut self, u: usize, depth: usize, i: i128, x: X) {
+        if depth == self.log {
+            self.data[u] = x;
+            return;
+        }
+        let side = (i >> (self.log - depth - 1)) as usize & 1;
+        let v = self.child(u, side, depth);
+        self.update_sub(v, depth + 1, i, x);
+        let [left, right] = self.children[u];
+        self.data[u] = self.value(left, depth + 1) * self.value(right, depth + 1);
+    }
+    fn value(&self, u: usize, depth: usize) -> X {
+        if u == NONE {
+            self.unit[depth]
+        } else {
+            self.data[u]
+        }
+    }
+    /// [i] = x
+    pub fn update(&mut self, i: i64, x: X) {
+        assert!(self.lo <= i && i < self.hi);
+        self.update_sub(0, 0, i as i128 - self.lo as i128, x);
+    }
+    fn product_sub(&self, u: usize, depth: usize, focus: i128, l: i128, r: i128) -> X {
+        let width = 1_i128 << (self.log - depth);
+        if focus + width <= l || r <= focus {
+            X::one()
+        } else if l <= focus && focus + width <= r {
+            self.value(u, depth)
+        } else {
+            let [left, right] = if u == NONE {
+                [NONE, NONE]
+            } else {
+                self.children[u]
+            };
+            let a = self.product_sub(left, depth + 1, focus, l, r);
+            let b = self.product_sub(right, depth + 1, focus + width / 2, l, r);
+            a * b
+        }
+    }
+    pub fn product<R: std::ops::RangeBounds<i64>>(&self, range: R) -> X {
+        let (l, r) = bounds(self.lo, self.hi, range);
+        if l >= r {
+            return X::one();
+        }
+        self.product_sub(0, 0, 0, l, r)
+    }
+    pub fn get(&self, i: i64) -> X {
+        self.product(i..=i)
+    }
+}
+
+/// Range update (with Act) & range product on [lo, hi) of i64.
+/// Nodes are made only on touched paths (O(log(hi - lo)) nodes for each operation).
+#[derive(Debug, Clone)]
+pub struct DynamicLazySegmentTree<X, M> {
+    lo: i64,
+    hi: i64,
+    log: usize,
+    unit: Vec<X>,
+    data: Vec<X>,
+    act: Vec<Option<M>>, // pending acts for children; None is identity
+    children: Vec<[usize; 2]>,
+}
+impl<X: Copy + Monoid, M: Copy + Monoid + Act<X>> DynamicLazySegmentTree<X, M> {
+    pub fn new(range: std::ops::Range<i64>) -> Self {
+        Self::with_default(range, X::one())
+    }
+    /// Every item is initially x
+    pub fn with_default(range: std::ops::Range<i64>, x: X) -> Self {
+        let (log, unit) = units(&range, x);
+        let mut tree = Self {
+            lo: range.start,
+            hi: range.end,
+            log,
+            unit,
+            data: vec![],
+            act: vec![],
+            children: vec![],
+        };
+        tree.node(0);
+        tree
+    }
+    fn node(&mut self, depth: usize) -> usize {
+        self.data.push(self.unit[depth]);
+        self.act.push(None);
+        self.children.push([NONE, NONE]);
+        self.data.len() - 1
+    }
+    /// Number of nodes made
+    pub fn nodes(&self) -> usize {
+        self.data.len()
+    }
+    fn apply(&mut self, u: usize, depth: usize, m: M) {
+        self.data[u] = m.act(self.data[u]);
+        if depth < self.log {
+            self.act[u] = Some(match self.act[u] {
+                Some(n) => n * m,
+                None => m,
+            });
+        }
+    }
+    /// Push the pending act down, making the children
+    fn push(&mut self, u: usize, depth: usize) {
+        for side in 0..2 {
+            if self.children[u][side] == NONE {
+                let v = self.node(depth + 1);
+                self.children[u][side] = v;
+            }
+        }
+        if let Some(m) = self.act[u].take() {
+            let [left, right] = self.children[u];
+            self.apply(left, depth + 1, m);
+            self.apply(right, depth + 1, m);
+        }
+    }
+    fn pull(&mut self, u: usize) {
+        let [left, right] = self.children[u];
+        self.data[u] = self.data[left] * self.data[right];
+    }
+    fn update_sub(&mut self, u: usize, depth: usize, focus: i128, l: i128, r: i128, m: M) {
+        let width = 1_i128 << (self.log - depth);
+        if focus + width <= l || r <= focus {
+            return;
+        }
+        if l <= focus && focus + width <= r {
+            self.apply(u, depth, m);
+            return;
+        }
+        self.push(u, depth);
+        let [left, right] = self.children[u];
+        self.update_sub(left, depth + 1, focus, l, r, m);
+        self.update_sub(right, depth + 1, focus + width / 2, l, r, m);
+        self.pull(u);
+    }
+    pub fn update<R: std::ops::RangeBounds<i64>>(&mut self, range: R, m: M) {
+        let (l, r) = bounds(self.lo, self.hi, range);
+        if l >= r {
+            return;
+        }
+        self.update_sub(0, 0, 0, l, r, m);
+    }
+    fn set_sub(&mut self, u: usize, depth: usize, i: i128, x: X) {
+        if depth == self.log {
+            self.data[u] = x;
+            return;
+        }
+        self.push(u, depth);
+        let side = (i >> (self.log - depth - 1)) as usize & 1;
+        self.set_sub(self.children[u][side], depth + 1, i, x);
+        self.pull(u);
+    }
+    /// [i] = x
+    pub fn set(&mut self, i: i64, x: X) {
+        assert!(self.lo <= i && i < self.hi);
+        self.set_sub(0, 0, i as i128 - self.lo as i128, x);
+    }
+    /// Product on range, without pushing acts down (nor making nodes)
+    fn product_sub(&self, u: usize, depth: usize, focus: i128, l: i128, r: i128) -> X {
+        let width = 1_i128 << (self.log - depth);
+        if focus + width <= l || r <= focus {
+            X::one()
+        } else if u == NONE {
+            // untouched; every item is unit[log]
+            let overlap = std::cmp::min(focus + width, r) - std::cmp::max(focus, l);
+            if overlap == width {
+                self.unit[depth]
+            } else {
+                let a = self.product_sub(NONE, depth + 1, focus, l, r);
+                let b = self.product_sub(NONE, depth + 1, focus + width / 2, l, r);
+                a * b
+            }
+        } else if l <= focus && focus + width <= r {
+            self.data[u]
+        } else {
+            let [left, right] = self.children[u];
+            let a = self.product_sub(left, depth + 1, focus, l, r);
+            let b = self.product_sub(right, depth + 1, focus + width / 2, l, r);
+            match self.act[u] {
+                Some(m) => m.act(a * b),
+                None => a * b,
+            }
+        }
+    }
+    pub fn product<R: std::ops::RangeBounds<i64>>(&self, range: R) -> X {
+        let (l, r) = bounds(self.lo, self.hi, range);
+        if l >= r {
+            return X::one();
+        }
+        self.product_sub(0, 0, 0, l, r)
+    }
+    pub fn get(&self, i: i64) -> X {
+        self.product(i..=i)
+    }
+}
+
+const NONE: usize = usize::MAX;
+
+/// (log, unit) s.t. the root has the width 2**log, and unit[d] is x^(2**(log-d))
+fn units<X: Copy + Monoid>(range: &std::ops::Range<i64>, x: X) -> (usize, Vec<X>) {
+    assert!(range.start < range.end);
+    let width = range.end as i128 - range.start as i128;
+    let mut log = 0;
+    while (1_i128 << log) < width {
+        log += 1;
+    }
+    let mut unit = vec![x; log + 1];
+    for d in (0..log).rev() {
+        unit[d] = unit[d + 1] * unit[d + 1];
+    }
+    (log, unit)
+}
+
+/// Offsets [l, r) from lo
+fn bounds<R: std::ops::RangeBounds<i64>>(lo: i64, hi: i64, range: R) -> (i128, i128) {
+    use std::ops::Bound::*;
+    let l = match range.start_bound() {
+        Included(&l) => l as i128,
+        Excluded(&l) => l as i128 + 1,
+        Unbounded => lo as i128,
+    };
+    let r = match range.end_bound() {
+        Included(&r) => r as i128 + 1,
+        Excluded(&r) => r as i128,
+        Unbounded => hi as i128,
+    };
+    assert!(lo as i128 <= l && r <= hi as i128);
+    (l - lo as i128, r - lo as i128)
+}
+
+#[cfg(test)]
+mod test_dynamic_segment_tree {
+    use crate::algebra::monoid_sum::*;
+    use crate::sequence::tree::dynamic_segment_tree::*;
+
+    #[test]
+    fn test_huge_indices() {
+        const INF: i64 = 1_000_000_000_000_000_000;
+        let mut st = DynamicSegmentTree::<Sum>::new(-INF..INF + 1);
+        st.update(-INF, Sum(1));
+        st.update(0, Sum(10));
+        st.update(INF, Sum(100));
+        st.update(12_345_678_901, Sum(1000));
+        assert_eq!(st.product(..), Sum(1111));
+        assert_eq!(st.product(-INF..0), Sum(1));
+        assert_eq!(st.product(0..=INF), Sum(1110));
+        assert_eq!(st.product(1..INF), Sum(1000));
+        assert_eq!(st.product(1..1), Sum(0));
+        assert_eq!(st.get(0), Sum(10));
+        st.update(0, Sum(-10));
+        assert_eq!(st.product(..), Sum(1091));
+        assert!(st.nodes() <= 4 * 62);
+        let st = DynamicSegmentTree::with_default(i64::MIN..i64::MAX, Sum(1));
+        assert_eq!(st.product(..), Sum(u64::MAX as i128));
+        assert_eq!(st.product(-5..5), Sum(10));
+    }
+
+    #[test]
+    fn test_lazy_random() {
+        use crate::algebra::act_add::*;
+        use crate::algebra::monoid_max::*;
+        use crate::num::random::xorshift::*;
+        let mut rand = XorShift::new();
+        let (lo, hi) = (-30_i64, 30_i64);
+        let n = (hi - lo) as usize;
+        let mut xs = vec![0_i64; n];
+        let mut st: DynamicLazySegmentTree<MaxInt<i64>, Add<MaxInt<i64>>> =
+            DynamicLazySegmentTree::with_default(lo..hi, MaxInt::Val(0));
+        for _ in 0..1000 {
+            let l = rand.gen::<usize>() % (n + 1);
+            let r = l + rand.gen::<usize>() % (n + 1 - l);
+            match rand.gen::<usize>() % 3 {
+                0 => {
+                    let x = rand.gen::<i64>() % 100;
+                    st.update(lo + l as i64..lo + r as i64, Add(MaxInt::Val(x)));
+                    for y in xs[l..r].iter_mut() {
+                        *y += x;
+                    }
+                }
+                1 if l < n => {
+                    let x = rand.gen::<i64>() % 100;
+                    st.set(lo + l as i64, MaxInt::Val(x));
+                    xs[l] = x;
+                }
+                _ => {}
+            }
+            let expected = xs[l..r].iter().map(|&x| MaxInt::Val(x)).product();
+            assert_eq!(st.product(lo + l as i64..lo + r as i64), expected);
+        }
+        let mut st: DynamicLazySegmentTree<MaxInt<i64>, Add<MaxInt<i64>>> =
+            DynamicLazySegmentTree::with_default(0..1_000_000_000_000, MaxInt::Val(0));
+        st.update(..500_000_000_000, Add(MaxInt::Val(3)));
+        st.update(100..=100, Add(MaxInt::Val(4)));
+        assert_eq!(st.product(..), MaxInt::Val(7));
+        assert_eq!(st.product(101..), MaxInt::Val(3));
+        assert_eq!(st.product(500_000_000_000..), MaxInt::Val(0));
+        assert_eq!(st.get(100), MaxInt::Val(7));
+    }
+}
@@ -0,0 +1,354 @@
+/// Sequence - Implicit Treap (sequence with insert/erase/reverse/split/merge)
+use crate::algebra::act::*;
+use crate::algebra::monoid::*;
+use crate::num::random::xorshift::*;
+
+type Link<X, M> = Option<Box<Node<X, M>>>;
+
+struct Node<X, M> {
+    value: X,
+    prod: X,  // of the subtree
+    rprod: X, // of the reversed subtree
+    size: usize,
+    priority: u64,
+    act: Option<M>, // pending act for children; None is identity
+    rev: bool,      // pending reversal for children
+    left: Link<X, M>,
+    right: Link<X, M>,
+}
+
+/// Sequence of X with O(log n) (expected) operations
+pub struct ImplicitTreap<X, M> {
+    root: Link<X, M>,
+    rand: XorShift,
+}
+impl<X: Copy + Monoid, M: Copy + Monoid + Act<X>> ImplicitTreap<X, M> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            rand: XorShift::new(),
+        }
+    }
+    pub fn from(xs: Vec<X>) -> Self {
+        let mut t = Self::new();
+        for x in xs {
+            t.push(x);
+        }
+        t
+    }
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+    fn bounds<R: std::ops::RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        use std::ops::Bound::*;
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r + 1,
+            Excluded(&r) => r,
+            Unbounded => self.len(),
+        };
+        assert!(l <= r && r <= self.len());
+        (l, r)
+    }
+    fn node(&mut self, x: X) -> Link<X, M> {
+        Some(Box::new(Node {
+            value: x,
+            prod: x,
+            rprod: x,
+            size: 1,
+            priority: self.rand.gen(),
+            act: None,
+            rev: false,
+            left: None,
+            right: None,
+        }))
+    }
+    /// Insert x at the position i (then self[i] == x)
+    pub fn insert(&mut self, i: usize, x: X) {
+        assert!(i <= self.len());
+        let (left, right) = split(self.root.take(), i);
+        let node = self.node(x);
+        self.root = merge(merge(left, node), right);
+    }
+    pub fn push(&mut self, x: X) {
+        let n = self.len();
+        self.insert(n, x);
+    }
+    /// Remove self[i]
+    pub fn erase(&mut self, i: usize) -> X {
+        assert!(i < self.len());
+        let (left, right) = split(self.root.take(), i);
+        let (mid, right) = split(right, 1);
+        self.root = merge(left, right);
+        mid.unwrap().value
+    }
+    /// Apply f on the middle of range
+    fn with_range<R: std::ops::RangeBounds<usize>, T, F: FnOnce(&mut Node<X, M>) -> T>(
+        &mut self,
+        range: R,
+        f: F,
+    ) -> Option<T> {
+        let (l, r) = self.bounds(range);
+        let (left, right) = split(self.root.take(), l);
+        let (mut mid, right) = split(right, r - l);
+        let result = mid.as_mut().map(|node| f(node));
+        self.root = merge(merge(left, mid), right);
+        result
+    }
+    pub fn product<R: std::ops::RangeBounds<usize>>(&mut self, range: R) -> X {
+        self.with_range(range, |node| node.prod)
+            .unwrap_or_else(X::one)
+    }
+    pub fn get(&mut self, i: usize) -> X {
+        self.product(i..=i)
+    }
+    pub fn update<R: std::ops::RangeBounds<usize>>(&mut self, range: R, m: M) {
+        self.with_range(range, |node| apply(node, m));
+    }
+    pub fn reverse<R: std::ops::RangeBounds<usize>>(&mut self, range: R) {
+        self.with_range(range, toggle);
+    }
+    /// self = self[..i], and returns self[i..]
+    pub fn split(&mut self, i: usize) -> Self {
+        assert!(i <= self.len());
+        let (left, right) = split(self.root.take(), i);
+        self.root = left;
+        Self {
+            root: right,
+            rand: XorShift::from_seed(self.rand.gen()),
+        }
+    }
+    /// self = self ++ other
+    pub fn merge(&mut self, other: Self) {
+        self.root = merge(self.root.take(), other.root);
+    }
+    pub fn to_vec(&self) -> Vec<X> {
+        let mut xs = Vec::with_capacity(self.len());
+        collect(&self.root, None, false, &mut xs);
+        xs
+    }
+    pub fn iter(&self) -> std::vec::IntoIter<X> {
+        self.to_vec().into_iter()
+    }
+}
+impl<X: Copy + Monoid, M: Copy + Monoid + Act<X>> Default for ImplicitTreap<X, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn size<X, M>(t: &Link<X, M>) -> usize {
+    t.as_ref().map_or(0, |node| node.size)
+}
+fn pull<X: Copy + Monoid, M>(node: &mut Node<X, M>) {
+    node.size = 1;
+    node.prod = node.value;
+    node.rprod = node.value;
+    if let Some(left) = &node.left {
+        node.size += left.size;
+        node.prod = left.prod * node.prod;
+        node.rprod = node.rprod * left.rprod;
+    }
+    if let Some(right) = &node.right {
+        node.size += right.size;
+        node.prod = node.prod * right.prod;
+        node.rprod = right.rprod * node.rprod;
+    }
+}
+fn apply<X: Copy + Monoid, M: Copy + Monoid + Act<X>>(node: &mut Node<X, M>, m: M) {
+    node.value = m.act(node.value);
+    node.prod = m.act(node.prod);
+    node.rprod = m.act(node.rprod);
+    node.act = Some(compose(node.act, m));
+}
+fn toggle<X, M>(node: &mut Node<X, M>) {
+    std::mem::swap(&mut node.left, &mut node.right);
+    std::mem::swap(&mut node.prod, &mut node.rprod);
+    node.rev = !node.rev;
+}
+fn push<X: Copy + Monoid, M: Copy + Monoid + Act<X>>(node: &mut Node<X, M>) {
+    if let Some(m) = node.act.take() {
+        for child in node.left.iter_mut().chain(node.right.iter_mut()) {
+            apply(child, m);
+        }
+    }
+    if node.rev {
+        node.rev = false;
+        for child in node.left.iter_mut().chain(node.right.iter_mut()) {
+            toggle(child);
+        }
+    }
+}
+/// (first k items, the rest)
+fn split<X: Copy + Monoid, M: Copy + Monoid + Act<X>>(
+    t: Link<X, M>,
+    k: usize,
+) -> (Link<X, M>, Link<X, M>) {
+    match t {
+        None => (None, None),
+        Some(mut node) => {
+            push(&mut node);
+            let left_size = size(&node.left);
+            if k <= left_size {
+                let (a, b) = split(node.left.take(), k);
+                node.left = b;
+                pull(&mut node);
+                (a, Some(node))
+            } else {
+                let (a, b) = split(node.right.take(), k - left_size - 1);
+                node.right = a;
+                pull(&mut node);
+                (Some(node), b)
+            }
+        }
+    }
+}
+fn merge<X: Copy + Monoid, M: Copy + Monoid + Act<X>>(a: Link<X, M>, b: Link<X, M>) -> Link<X, M> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority > b.priority {
+                push(&mut a);
+                a.right = merge(a.right.take(), Some(b));
+                pull(&mut a);
+                Some(a)
+            } else {
+                push(&mut b);
+                b.left = merge(Some(a), b.left.take());
+                pull(&mut b);
+                Some(b)
+            }
+        }
+    }
+}
+/// In-order values, with pending act m and reversal rev from the ancestors
+fn collect<X: Copy + Monoid, M: Copy + Monoid + Act<X>>(
+    t: &Link<X, M>,
+    m: Option<M>,
+    rev: bool,
+    xs: &mut Vec<X>,
+) {
+    if let Some(node) = t {
+        let act = match (node.act, m) {
+            (Some(n), Some(m)) => Some(n * m),
+            (n, None) => n,
+            (None, m) => m,
+        };
+        let rev_children = node.rev ^ rev;
+        let (first, second) = if rev {
+            (&node.right, &node.left)
+        } else {
+            (&node.left, &node.right)
+        };
+        collect(first, act, rev_children, xs);
+        xs.push(match m {
+            Some(m) => m.act(node.value),
+            None => node.value,
+        });
+        collect(second, act, rev_children, xs);
+    }
+}
+/// m then n
+fn compose<M: Monoid>(m: Option<M>, n: M) -> M {
+    match m {
+        Some(m) => m * n,
+        None => n,
+    }
+}
+
+#[cfg(test)]
+mod test_implicit_treap {
+    use crate::algebra::act_add::*;
+    use crate::algebra::act_assign::*;
+    use crate::algebra::monoid_min::*;
+    use crate::monoid; // IGNORE
+    use crate::sequence::tree::implicit_treap::*;
+
+    #[test]
+    fn test_random_ops() {
+        let mut rand = XorShift::new();
+        let mut xs: Vec<i64> = vec![];
+        let mut t: ImplicitTreap<MinInt<i64>, Add<MinInt<i64>>> = ImplicitTreap::new();
+        for _ in 0..2000 {
+            let n = xs.len();
+            let l = rand.gen::<usize>() % (n + 1);
+            let r = l + rand.gen::<usize>() % (n + 1 - l);
+            match rand.gen::<usize>() % 5 {
+                0 | 1 => {
+                    let x = rand.gen::<i64>() % 100;
+                    t.insert(l, MinInt::Val(x));
+                    xs.insert(l, x);
+                }
+                2 if l < n => {
+                    assert_eq!(t.erase(l), MinInt::Val(xs.remove(l)));
+                }
+                3 => {
+                    let x = rand.gen::<i64>() % 100;
+                    t.update(l..r, Add(MinInt::Val(x)));
+                    xs[l..r].iter_mut().for_each(|y| *y += x);
+                }
+                _ => {
+                    t.reverse(l..r);
+                    xs[l..r].reverse();
+                }
+            }
+            assert_eq!(t.len(), xs.len());
+            let l = rand.gen::<usize>() % (xs.len() + 1);
+            let r = l + rand.gen::<usize>() % (xs.len() + 1 - l);
+            let expected = xs[l..r].iter().map(|&x| MinInt::Val(x)).product();
+            assert_eq!(t.product(l..r), expected);
+            assert_eq!(
+                t.iter().map(|x| x.unwrap()).collect::<Vec<_>>(),
+                xs,
+                "to_vec"
+            );
+        }
+    }
+
+    /// Polynomial hash of a sequence (non-commutative)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Hash(u64, u64); // (hash, BASE^len)
+    const BASE: u64 = 1_000_003;
+    monoid! {
+        Hash;
+        one = Hash(0, 1);
+        mul(self, other) = {
+            Hash(self.0.wrapping_mul(other.1).wrapping_add(other.0), self.1.wrapping_mul(other.1))
+        };
+    }
+    fn hash(xs: &[u64]) -> Hash {
+        xs.iter().map(|&x| Hash(x, BASE)).product()
+    }
+
+    #[test]
+    fn test_split_merge_reverse() {
+        let xs: Vec<u64> = (0..10).collect();
+        let mut t: ImplicitTreap<Hash, Assign<Hash>> =
+            ImplicitTreap::from(xs.iter().map(|&x| Hash(x, BASE)).collect());
+        assert_eq!(t.product(..), hash(&xs));
+        t.reverse(2..8); // 0 1 7 6 5 4 3 2 8 9
+        assert_eq!(t.product(..), hash(&[0, 1, 7, 6, 5, 4, 3, 2, 8, 9]));
+        assert_eq!(t.product(1..4), hash(&[1, 7, 6]));
+        t.reverse(..5); // 5 6 7 1 0 4 3 2 8 9
+        assert_eq!(t.product(..), hash(&[5, 6, 7, 1, 0, 4, 3, 2, 8, 9]));
+        let mut u = t.split(4); // [5 6 7 1], [0 4 3 2 8 9]
+        assert_eq!(t.len(), 4);
+        assert_eq!(u.len(), 6);
+        assert_eq!(t.product(..), hash(&[5, 6, 7, 1]));
+        assert_eq!(u.product(..), hash(&[0, 4, 3, 2, 8, 9]));
+        u.reverse(..);
+        u.merge(t); // 9 8 2 3 4 0 5 6 7 1
+        assert_eq!(u.product(..), hash(&[9, 8, 2, 3, 4, 0, 5, 6, 7, 1]));
+        assert_eq!(u.get(5), Hash(0, BASE));
+        let ys: Vec<u64> = u.iter().map(|h| h.0).collect();
+        assert_eq!(ys, vec![9, 8, 2, 3, 4, 0, 5, 6, 7, 1]);
+    }
+}
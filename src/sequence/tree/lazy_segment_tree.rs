@@ -1,4 +1,4 @@
-/// Sequence - Lazy Segment Tree
+/// Sequence - Lazy Segment Tree (bottom-up, non-recursive)
 use crate::algebra::act::*;
 use crate::algebra::monoid::*;
 
@@ -6,106 +6,358 @@ use crate::algebra::monoid::*;
 pub struct LazySegmentTree<X, M> {
     length: usize,       // of leaves
     length_upper: usize, // power of 2
-    size: usize,         // of nodes
-    data: Vec<X>,
-    act: Vec<M>,
+    log: usize,          // length_upper == 2**log
+    data: Vec<X>,        // 1-indexed heap; leaves are data[length_upper..]
+    act: Vec<Option<M>>, // pending acts for children; None is identity
 }
 impl<X: Copy + Monoid, M: Copy + Monoid + Act<X>> LazySegmentTree<X, M> {
     pub fn new(length: usize) -> Self {
         let mut length_upper = 1;
+        let mut log = 0;
         while length_upper < length {
             length_upper *= 2;
+            log += 1;
         }
-        let size = length_upper * 2 - 1;
-        let data = vec![X::one(); size];
-        let act = vec![M::one(); size];
+        let data = vec![X::one(); length_upper * 2];
+        let act = vec![None; length_upper];
         LazySegmentTree {
             length,
             length_upper,
-            size,
+            log,
             data,
             act,
         }
     }
     pub fn from(xs: Vec<X>) -> Self {
         let mut tree = Self::new(xs.len());
-        for i in 0..xs.len() {
-            tree.data[tree.size / 2 + i] = xs[i];
+        for (i, &x) in xs.iter().enumerate() {
+            tree.data[tree.length_upper + i] = x;
         }
-        for i in (0..tree.size / 2).rev() {
-            tree.data[i] = tree.data[2 * i + 1] * tree.data[2 * i + 2];
+        for k in (1..tree.length_upper).rev() {
+            tree.pull(k);
         }
         tree
     }
-    fn propagation(&mut self, idx: usize) {
-        if idx < self.size / 2 {
-            self.act[idx * 2 + 1] = self.act[idx * 2 + 1] * self.act[idx];
-            self.act[idx * 2 + 2] = self.act[idx * 2 + 2] * self.act[idx];
-        }
-        self.data[idx] = self.act[idx].act(self.data[idx]);
-        self.act[idx] = M::one();
-    }
-    fn update_sub(
-        &mut self,
-        range: std::ops::Range<usize>,
-        m: M,
-        idx: usize,
-        focus: std::ops::Range<usize>,
-    ) {
-        self.propagation(idx);
-        if focus.end <= range.start || range.end <= focus.start {
+    fn bounds<R: std::ops::RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        use std::ops::Bound::*;
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r + 1,
+            Excluded(&r) => r,
+            Unbounded => self.length,
+        };
+        assert!(r <= self.length);
+        (l, r)
+    }
+    fn pull(&mut self, k: usize) {
+        self.data[k] = self.data[2 * k] * self.data[2 * k + 1];
+    }
+    fn apply(&mut self, k: usize, m: M) {
+        self.data[k] = m.act(self.data[k]);
+        if k < self.length_upper {
+            self.act[k] = Some(compose(self.act[k], m));
+        }
+    }
+    fn push(&mut self, k: usize) {
+        if let Some(m) = self.act[k].take() {
+            self.apply(2 * k, m);
+            self.apply(2 * k + 1, m);
+        }
+    }
+    /// Pending acts over the ancestors of the leaf;
+    /// pending[h] is for a node at the height h - 1 on the path
+    fn pending(&self, leaf: usize) -> Vec<Option<M>> {
+        let mut pending = vec![None; self.log + 2];
+        for h in (1..=self.log).rev() {
+            pending[h] = match (self.act[leaf >> h], pending[h + 1]) {
+                (Some(m), Some(n)) => Some(m * n),
+                (m, None) => m,
+                (None, n) => n,
+            };
+        }
+        pending
+    }
+    pub fn get(&self, i: usize) -> X {
+        assert!(i < self.length);
+        let mut k = i + self.length_upper;
+        let mut x = self.data[k];
+        while k > 1 {
+            k >>= 1;
+            if let Some(m) = self.act[k] {
+                x = m.act(x);
+            }
+        }
+        x
+    }
+    /// Alias of get
+    pub fn index(&self, i: usize) -> X {
+        self.get(i)
+    }
+    pub fn set(&mut self, i: usize, x: X) {
+        assert!(i < self.length);
+        let k = i + self.length_upper;
+        for h in (1..=self.log).rev() {
+            self.push(k >> h);
+        }
+        self.data[k] = x;
+        for h in 1..=self.log {
+            self.pull(k >> h);
+        }
+    }
+    pub fn update<R: std::ops::RangeBounds<usize>>(&mut self, range: R, m: M) {
+        let (l, r) = self.bounds(range);
+        if l >= r {
             return;
         }
-        if range.start <= focus.start && focus.end <= range.end {
-            self.act[idx] = self.act[idx] * m;
-            self.propagation(idx);
-        } else if idx < self.data.len() / 2 {
-            let mid = (focus.start + focus.end) / 2;
-            self.update_sub(range.clone(), m, idx * 2 + 1, focus.start..mid);
-            self.update_sub(range.clone(), m, idx * 2 + 2, mid..focus.end);
-            self.data[idx] = self.data[idx * 2 + 1] * self.data[idx * 2 + 2];
-        }
-    }
-    pub fn update(&mut self, range: std::ops::Range<usize>, m: M) {
-        self.update_sub(range, m, 0, 0..self.length_upper);
-    }
-    fn product_sub(
-        &mut self,
-        range: std::ops::Range<usize>,
-        idx: usize,
-        focus: std::ops::Range<usize>,
-    ) -> X {
-        self.propagation(idx);
-        if focus.end <= range.start || range.end <= focus.start {
-            X::one()
-        } else if range.start <= focus.start && focus.end <= range.end {
-            self.data[idx]
-        } else {
-            let mid = (focus.start + focus.end) / 2;
-            let a = self.product_sub(range.clone(), idx * 2 + 1, focus.start..mid);
-            let b = self.product_sub(range.clone(), idx * 2 + 2, mid..focus.end);
-            a * b
-        }
-    }
-    pub fn product(&mut self, range: std::ops::Range<usize>) -> X {
-        self.product_sub(range, 0, 0..self.length_upper)
-    }
-    pub fn index(&mut self, i: usize) -> X {
-        self.product(i..i + 1)
-    }
-    pub fn to_vec(&mut self) -> Vec<X> {
-        (0..self.length).map(|i| self.index(i)).collect()
+        let (l, r) = (l + self.length_upper, r + self.length_upper);
+        for h in (1..=self.log).rev() {
+            if ((l >> h) << h) != l {
+                self.push(l >> h);
+            }
+            if ((r >> h) << h) != r {
+                self.push((r - 1) >> h);
+            }
+        }
+        {
+            let (mut l, mut r) = (l, r);
+            while l < r {
+                if l & 1 == 1 {
+                    self.apply(l, m);
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    self.apply(r, m);
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+        for h in 1..=self.log {
+            if ((l >> h) << h) != l {
+                self.pull(l >> h);
+            }
+            if ((r >> h) << h) != r {
+                self.pull((r - 1) >> h);
+            }
+        }
+    }
+    /// Product on range, without pushing acts down
+    pub fn product<R: std::ops::RangeBounds<usize>>(&self, range: R) -> X {
+        let (l, r) = self.bounds(range);
+        if l >= r {
+            return X::one();
+        }
+        let (mut l, mut r) = (l + self.length_upper, r + self.length_upper);
+        let pending_l = self.pending(l);
+        let pending_r = self.pending(r - 1);
+        let act = |m: Option<M>, x: X| match m {
+            Some(m) => m.act(x),
+            None => x,
+        };
+        let mut sml = X::one();
+        let mut smr = X::one();
+        let mut h = 0;
+        while l < r {
+            if l & 1 == 1 {
+                sml = sml * act(pending_l[h + 1], self.data[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                smr = act(pending_r[h + 1], self.data[r]) * smr;
+            }
+            l >>= 1;
+            r >>= 1;
+            h += 1;
+        }
+        sml * smr
+    }
+    pub fn all_product(&self) -> X {
+        self.data[1]
+    }
+    /// Max r s.t. pred(product(l..r)), with O(log n)
+    /// pred should be monotone and pred(X::one()) == true
+    pub fn max_right<F: Fn(X) -> bool>(&mut self, l: usize, pred: F) -> usize {
+        assert!(l <= self.length);
+        if l == self.length {
+            return self.length;
+        }
+        let mut k = l + self.length_upper;
+        for h in (1..=self.log).rev() {
+            self.push(k >> h);
+        }
+        let mut acc = X::one();
+        loop {
+            while k & 1 == 0 {
+                k >>= 1;
+            }
+            if !pred(acc * self.data[k]) {
+                while k < self.length_upper {
+                    self.push(k);
+                    k *= 2;
+                    if pred(acc * self.data[k]) {
+                        acc = acc * self.data[k];
+                        k += 1;
+                    }
+                }
+                return std::cmp::min(k - self.length_upper, self.length);
+            }
+            acc = acc * self.data[k];
+            k += 1;
+            if k.is_power_of_two() {
+                return self.length;
+            }
+        }
+    }
+    /// Min l s.t. pred(product(l..r)), with O(log n)
+    /// pred should be monotone and pred(X::one()) == true
+    pub fn min_left<F: Fn(X) -> bool>(&mut self, r: usize, pred: F) -> usize {
+        assert!(r <= self.length);
+        if r == 0 {
+            return 0;
+        }
+        let mut k = r + self.length_upper;
+        for h in (1..=self.log).rev() {
+            self.push((k - 1) >> h);
+        }
+        let mut acc = X::one();
+        loop {
+            k -= 1;
+            while k > 1 && k & 1 == 1 {
+                k >>= 1;
+            }
+            if !pred(self.data[k] * acc) {
+                while k < self.length_upper {
+                    self.push(k);
+                    k = 2 * k + 1;
+                    if pred(self.data[k] * acc) {
+                        acc = self.data[k] * acc;
+                        k -= 1;
+                    }
+                }
+                return k + 1 - self.length_upper;
+            }
+            acc = self.data[k] * acc;
+            if k.is_power_of_two() {
+                return 0;
+            }
+        }
+    }
+    pub fn to_vec(&self) -> Vec<X> {
+        (0..self.length).map(|i| self.get(i)).collect()
+    }
+}
+/// m then n
+fn compose<M: Monoid>(m: Option<M>, n: M) -> M {
+    match m {
+        Some(m) => m * n,
+        None => n,
     }
 }
 impl<X: std::fmt::Debug, M: std::fmt::Debug> LazySegmentTree<X, M> {
     pub fn debug(&self) {
         #[cfg(debug_assertions)]
-        for i in 0..self.size {
-            if i > 0 && (i + 1).count_ones() == 1 {
+        for k in 1..self.data.len() {
+            if k > 1 && k.count_ones() == 1 {
                 eprintln!();
             }
-            eprint!("{:?} / {:?}; ", &self.data[i], &self.act[i]);
+            if k < self.length_upper {
+                eprint!("{:?} / {:?}; ", &self.data[k], &self.act[k]);
+            } else {
+                eprint!("{:?}; ", &self.data[k]);
+            }
         }
         eprintln!();
     }
 }
+
+#[cfg(test)]
+mod test_lazy_segment_tree {
+    use crate::algebra::act_add::*;
+    use crate::algebra::monoid_max::*;
+    use crate::sequence::tree::lazy_segment_tree::*;
+
+    #[test]
+    fn test_max_right_min_left() {
+        let mut xs: Vec<i64> = vec![3, 1, 4, 1, 5, 9, 2, 6, 5];
+        let mut st: LazySegmentTree<MaxInt<i64>, Add<MaxInt<i64>>> =
+            LazySegmentTree::from(xs.iter().map(|&x| MaxInt::Val(x)).collect());
+        st.update(2..6, Add(MaxInt::Val(-3)));
+        for x in xs[2..6].iter_mut() {
+            *x -= 3;
+        }
+        assert_eq!(st.index(3), MaxInt::Val(-2));
+        for k in 0..10 {
+            let pred = |m: MaxInt<i64>| m <= MaxInt::Val(k);
+            for l in 0..=xs.len() {
+                let mut r = l;
+                while r < xs.len() && xs[r] <= k {
+                    r += 1;
+                }
+                assert_eq!(st.max_right(l, pred), r);
+            }
+            for r in 0..=xs.len() {
+                let mut l = r;
+                while l > 0 && xs[l - 1] <= k {
+                    l -= 1;
+                }
+                assert_eq!(st.min_left(r, pred), l);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_ops() {
+        use crate::algebra::act_assign::*;
+        use crate::algebra::monoid_min::*;
+        use crate::num::random::xorshift::*;
+        let mut rand = XorShift::new();
+        for n in 1..20 {
+            let mut xs: Vec<i64> = (0..n).map(|_| rand.gen::<i64>() % 100).collect();
+            let mut st: LazySegmentTree<MinInt<i64>, Assign<MinInt<i64>>> =
+                LazySegmentTree::from(xs.iter().map(|&x| MinInt::Val(x)).collect());
+            for _ in 0..100 {
+                let l = rand.gen::<usize>() % (n + 1);
+                let r = l + rand.gen::<usize>() % (n + 1 - l);
+                match rand.gen::<usize>() % 4 {
+                    0 => {
+                        let x = rand.gen::<i64>() % 100;
+                        st.update(l..r, Assign::Some(MinInt::Val(x)));
+                        for y in xs[l..r].iter_mut() {
+                            *y = x;
+                        }
+                    }
+                    1 if l < n => {
+                        let x = rand.gen::<i64>() % 100;
+                        st.set(l, MinInt::Val(x));
+                        xs[l] = x;
+                    }
+                    _ => {}
+                }
+                let expected = xs[l..r].iter().map(|&x| MinInt::Val(x)).product();
+                assert_eq!(st.product(l..r), expected);
+                if r > l {
+                    assert_eq!(st.product(l..=r - 1), expected);
+                }
+                assert_eq!(
+                    st.product(l..),
+                    xs[l..].iter().map(|&x| MinInt::Val(x)).product()
+                );
+                assert_eq!(
+                    st.product(..r),
+                    xs[..r].iter().map(|&x| MinInt::Val(x)).product()
+                );
+                assert_eq!(st.all_product(), st.product(..));
+                assert_eq!(
+                    st.to_vec(),
+                    xs.iter().map(|&x| MinInt::Val(x)).collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+}
@@ -1,11 +1,21 @@
 pub mod bit;
+pub mod bit_2d;
 pub mod bit_cumulative;
+pub mod bit_range;
+pub mod disjoint_sparse_table;
+pub mod dynamic_segment_tree;
+pub mod implicit_treap;
 pub mod lazy_segment_tree;
+pub mod persistent_segment_tree;
 pub mod ranged_add_rmq;
 pub mod ranged_add_segment_tree_sum;
 pub mod ranged_assign_rmq;
+pub mod ranged_chmin_chmax_segment_tree;
 pub mod rmq;
 pub mod segment_tree;
+pub mod segment_tree_beats;
 pub mod segment_tree_prod;
 pub mod segment_tree_sum;
 pub mod sparse_table;
+pub mod sparse_table_2d;
+pub mod wavelet_matrix;
@@ -0,0 +1,180 @@
+/// Sequence - Persistent Segment Tree (path-copying)
+use crate::algebra::monoid::*;
+
+/// Every update makes a new version (handle: usize);
+/// the initial version is 0
+#[derive(Debug, Clone)]
+pub struct PersistentSegmentTree<X> {
+    length: usize,
+    data: Vec<X>,
+    children: Vec<(usize, usize)>, // of each node; leaves have (0, 0)
+    roots: Vec<usize>,             // of each version
+}
+impl<X: Copy + Monoid> PersistentSegmentTree<X> {
+    pub fn new(length: usize) -> Self {
+        Self::from(vec![X::one(); length])
+    }
+    pub fn from(xs: Vec<X>) -> Self {
+        let mut tree = Self {
+            length: xs.len(),
+            data: vec![],
+            children: vec![],
+            roots: vec![],
+        };
+        let root = tree.build(&xs, 0..std::cmp::max(1, xs.len()));
+        tree.roots.push(root);
+        tree
+    }
+    fn node(&mut self, x: X, children: (usize, usize)) -> usize {
+        self.data.push(x);
+        self.children.push(children);
+        self.data.len() - 1
+    }
+    fn build(&mut self, xs: &[X], focus: std::ops::Range<usize>) -> usize {
+        if focus.end - focus.start == 1 {
+            let x = if focus.start < xs.len() {
+                xs[focus.start]
+            } else {
+                X::one()
+            };
+            return self.node(x, (0, 0));
+        }
+        let mid = (focus.start + focus.end) / 2;
+        let left = self.build(xs, focus.start..mid);
+        let right = self.build(xs, mid..focus.end);
+        self.node(self.data[left] * self.data[right], (left, right))
+    }
+    /// Number of versions
+    pub fn versions(&self) -> usize {
+        self.roots.len()
+    }
+    fn update_sub(&mut self, u: usize, i: usize, x: X, focus: std::ops::Range<usize>) -> usize {
+        if focus.end - focus.start == 1 {
+            return self.node(x, (0, 0));
+        }
+        let mid = (focus.start + focus.end) / 2;
+        let (mut left, mut right) = self.children[u];
+        if i < mid {
+            left = self.update_sub(left, i, x, focus.start..mid);
+        } else {
+            right = self.update_sub(right, i, x, mid..focus.end);
+        }
+        self.node(self.data[left] * self.data[right], (left, right))
+    }
+    /// Make a new version from `version` with [i] = x, and returns its handle
+    pub fn update(&mut self, version: usize, i: usize, x: X) -> usize {
+        assert!(i < self.length);
+        let root = self.update_sub(self.roots[version], i, x, 0..self.length);
+        self.roots.push(root);
+        self.roots.len() - 1
+    }
+    fn product_sub(
+        &self,
+        u: usize,
+        range: &std::ops::Range<usize>,
+        focus: std::ops::Range<usize>,
+    ) -> X {
+        if focus.end <= range.start || range.end <= focus.start {
+            X::one()
+        } else if range.start <= focus.start && focus.end <= range.end {
+            self.data[u]
+        } else {
+            let mid = (focus.start + focus.end) / 2;
+            let (left, right) = self.children[u];
+            let a = self.product_sub(left, range, focus.start..mid);
+            let b = self.product_sub(right, range, mid..focus.end);
+            a * b
+        }
+    }
+    pub fn product(&self, version: usize, range: std::ops::Range<usize>) -> X {
+        if range.start >= range.end {
+            return X::one();
+        }
+        self.product_sub(self.roots[version], &range, 0..self.length)
+    }
+    pub fn get(&self, version: usize, i: usize) -> X {
+        self.product(version, i..i + 1)
+    }
+    /// For counting trees (count(x) is the number of items in a node),
+    /// min i s.t. sum_{j <= i} (count(new[j]) - count(old[j])) > k
+    /// e.g. k-th (0-indexed) smallest value in a[l..r] where version t has counts of a[..t]
+    pub fn kth_diff<F: Fn(X) -> i128>(
+        &self,
+        old: usize,
+        new: usize,
+        k: i128,
+        count: F,
+    ) -> Option<usize> {
+        let (mut u, mut v) = (self.roots[old], self.roots[new]);
+        if count(self.data[v]) - count(self.data[u]) <= k {
+            return None;
+        }
+        let mut k = k;
+        let mut focus = 0..self.length;
+        while focus.end - focus.start > 1 {
+            let mid = (focus.start + focus.end) / 2;
+            let (ul, ur) = self.children[u];
+            let (vl, vr) = self.children[v];
+            let c = count(self.data[vl]) - count(self.data[ul]);
+            if k < c {
+                u = ul;
+                v = vl;
+                focus = focus.start..mid;
+            } else {
+                k -= c;
+                u = ur;
+                v = vr;
+                focus = mid..focus.end;
+            }
+        }
+        Some(focus.start)
+    }
+}
+
+#[cfg(test)]
+mod test_persistent_segment_tree {
+    use crate::algebra::monoid_sum::*;
+    use crate::sequence::tree::persistent_segment_tree::*;
+
+    #[test]
+    fn test_versions() {
+        let mut st = PersistentSegmentTree::from(vec![Sum(1), Sum(2), Sum(3), Sum(4), Sum(5)]);
+        let v1 = st.update(0, 2, Sum(10)); // [1, 2, 10, 4, 5]
+        let v2 = st.update(v1, 0, Sum(0)); // [0, 2, 10, 4, 5]
+        let v3 = st.update(0, 4, Sum(-5)); // [1, 2, 3, 4, -5]
+        assert_eq!(st.versions(), 4);
+        assert_eq!(st.product(0, 0..5), Sum(15));
+        assert_eq!(st.product(v1, 0..5), Sum(22));
+        assert_eq!(st.product(v2, 0..5), Sum(21));
+        assert_eq!(st.product(v3, 0..5), Sum(5));
+        assert_eq!(st.product(v1, 1..3), Sum(12));
+        assert_eq!(st.product(v3, 3..3), Sum(0));
+        assert_eq!(st.get(v2, 0), Sum(0));
+        assert_eq!(st.get(v3, 0), Sum(1));
+    }
+
+    #[test]
+    fn test_kth_smallest() {
+        let a = vec![5, 1, 4, 1, 3, 9, 2, 6];
+        let mut values = a.clone();
+        values.sort();
+        values.dedup();
+        let mut st = PersistentSegmentTree::<Sum>::new(values.len());
+        // version t has the counts of a[..t]
+        for (t, x) in a.iter().enumerate() {
+            let i = values.binary_search(x).unwrap();
+            let c = st.get(t, i);
+            st.update(t, i, Sum(c.0 + 1));
+        }
+        for l in 0..a.len() {
+            for r in l..=a.len() {
+                let mut sorted = a[l..r].to_vec();
+                sorted.sort();
+                for k in 0..=sorted.len() {
+                    let kth = st.kth_diff(l, r, k as i128, |s| s.0).map(|i| values[i]);
+                    assert_eq!(kth, sorted.get(k).cloned());
+                }
+            }
+        }
+    }
+}
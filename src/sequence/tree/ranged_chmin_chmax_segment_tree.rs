@@ -0,0 +1,271 @@
+/// Sequence - Segment Tree Beats - Ranged Chmin/Chmax/Add/Assign, Sum/Min/Max
+use crate::algebra::act::*;
+use crate::algebra::monoid::*;
+use crate::monoid; // IGNORE
+use crate::sequence::tree::segment_tree_beats::*;
+
+pub struct RangedChminChmaxSegmentTree {
+    pub t: SegmentTreeBeats<BeatsNode, BeatsAct>,
+}
+
+const NEG_INF: i64 = i64::MIN;
+const INF: i64 = i64::MAX;
+
+/// Summary of a range; max2/min2 are the second distinct values (or -inf/+inf)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeatsNode {
+    pub sum: i64,
+    pub len: i64,
+    pub max: i64,
+    max2: i64,
+    max_count: i64,
+    pub min: i64,
+    min2: i64,
+    min_count: i64,
+}
+impl BeatsNode {
+    pub fn new(x: i64) -> Self {
+        Self {
+            sum: x,
+            len: 1,
+            max: x,
+            max2: NEG_INF,
+            max_count: 1,
+            min: x,
+            min2: INF,
+            min_count: 1,
+        }
+    }
+}
+monoid! {
+    BeatsNode;
+    one = BeatsNode {
+        sum: 0,
+        len: 0,
+        max: NEG_INF,
+        max2: NEG_INF,
+        max_count: 0,
+        min: INF,
+        min2: INF,
+        min_count: 0,
+    };
+    mul(self, other) = {
+        if self.len == 0 {
+            return other;
+        }
+        if other.len == 0 {
+            return self;
+        }
+        use std::cmp::Ordering::*;
+        let (max, max2, max_count) = match self.max.cmp(&other.max) {
+            Greater => (self.max, self.max2.max(other.max), self.max_count),
+            Less => (other.max, self.max.max(other.max2), other.max_count),
+            Equal => (self.max, self.max2.max(other.max2), self.max_count + other.max_count),
+        };
+        let (min, min2, min_count) = match self.min.cmp(&other.min) {
+            Less => (self.min, self.min2.min(other.min), self.min_count),
+            Greater => (other.min, self.min.min(other.min2), other.min_count),
+            Equal => (self.min, self.min2.min(other.min2), self.min_count + other.min_count),
+        };
+        BeatsNode {
+            sum: self.sum + other.sum,
+            len: self.len + other.len,
+            max,
+            max2,
+            max_count,
+            min,
+            min2,
+            min_count,
+        }
+    };
+}
+
+/// x -> min(max(x + add, lo), hi)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeatsAct {
+    add: i64,
+    lo: i64,
+    hi: i64,
+}
+impl BeatsAct {
+    pub fn add(x: i64) -> Self {
+        Self {
+            add: x,
+            lo: NEG_INF,
+            hi: INF,
+        }
+    }
+    pub fn chmin(x: i64) -> Self {
+        Self {
+            add: 0,
+            lo: NEG_INF,
+            hi: x,
+        }
+    }
+    pub fn chmax(x: i64) -> Self {
+        Self {
+            add: 0,
+            lo: x,
+            hi: INF,
+        }
+    }
+    pub fn assign(x: i64) -> Self {
+        Self {
+            add: 0,
+            lo: x,
+            hi: x,
+        }
+    }
+}
+/// x + a, keeping infinities
+fn shift(x: i64, a: i64) -> i64 {
+    if x == NEG_INF || x == INF {
+        x
+    } else {
+        x + a
+    }
+}
+monoid! {
+    BeatsAct;
+    one = BeatsAct::add(0);
+    mul(self, other) = {
+        let clamp = |x: i64| x.max(other.lo).min(other.hi);
+        BeatsAct {
+            add: std::ops::Add::add(self.add, other.add),
+            lo: clamp(shift(self.lo, other.add)),
+            hi: clamp(shift(self.hi, other.add)),
+        }
+    };
+}
+impl FailableAct<BeatsNode> for BeatsAct {
+    fn try_act(&self, x: BeatsNode) -> Option<BeatsNode> {
+        if x.len == 0 {
+            return Some(x);
+        }
+        let mut x = x;
+        if self.add != 0 {
+            x.sum += self.add * x.len;
+            x.max += self.add;
+            x.max2 = shift(x.max2, self.add);
+            x.min += self.add;
+            x.min2 = shift(x.min2, self.add);
+        }
+        if self.lo > x.min {
+            if self.lo >= x.min2 {
+                return None;
+            }
+            x.sum += (self.lo - x.min) * x.min_count;
+            if x.max == x.min {
+                x.max = self.lo;
+            } else if x.max2 == x.min {
+                x.max2 = self.lo;
+            }
+            x.min = self.lo;
+        }
+        if self.hi < x.max {
+            if self.hi <= x.max2 {
+                return None;
+            }
+            x.sum -= (x.max - self.hi) * x.max_count;
+            if x.min == x.max {
+                x.min = self.hi;
+            } else if x.min2 == x.max {
+                x.min2 = self.hi;
+            }
+            x.max = self.hi;
+        }
+        Some(x)
+    }
+}
+
+impl RangedChminChmaxSegmentTree {
+    pub fn from(v: Vec<i64>) -> Self {
+        let t = SegmentTreeBeats::from(v.iter().map(|&x| BeatsNode::new(x)).collect());
+        Self { t }
+    }
+    pub fn to_vec(&self) -> Vec<i64> {
+        self.t.to_vec().iter().map(|node| node.sum).collect()
+    }
+    /// x[i] = min(x[i], y) for i in range
+    pub fn chmin(&mut self, range: std::ops::Range<usize>, y: i64) {
+        self.t.update(range, BeatsAct::chmin(y));
+    }
+    /// x[i] = max(x[i], y) for i in range
+    pub fn chmax(&mut self, range: std::ops::Range<usize>, y: i64) {
+        self.t.update(range, BeatsAct::chmax(y));
+    }
+    pub fn add(&mut self, range: std::ops::Range<usize>, y: i64) {
+        self.t.update(range, BeatsAct::add(y));
+    }
+    pub fn assign(&mut self, range: std::ops::Range<usize>, y: i64) {
+        self.t.update(range, BeatsAct::assign(y));
+    }
+    pub fn sum(&mut self, range: std::ops::Range<usize>) -> i64 {
+        self.t.product(range).sum
+    }
+    /// min of range (i64::MAX for empty range)
+    pub fn min(&mut self, range: std::ops::Range<usize>) -> i64 {
+        self.t.product(range).min
+    }
+    /// max of range (i64::MIN for empty range)
+    pub fn max(&mut self, range: std::ops::Range<usize>) -> i64 {
+        self.t.product(range).max
+    }
+}
+
+#[cfg(test)]
+mod test_ranged_chmin_chmax_segment_tree {
+    use crate::num::random::xorshift::*;
+    use crate::sequence::tree::ranged_chmin_chmax_segment_tree::*;
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for n in 1..30 {
+            let mut xs: Vec<i64> = (0..n).map(|_| rand.gen::<i64>() % 100).collect();
+            let mut st = RangedChminChmaxSegmentTree::from(xs.clone());
+            for _ in 0..200 {
+                let l = rand.gen::<usize>() % (n + 1);
+                let r = l + rand.gen::<usize>() % (n + 1 - l);
+                let y = rand.gen::<i64>() % 100;
+                match rand.gen::<usize>() % 4 {
+                    0 => {
+                        st.chmin(l..r, y);
+                        xs[l..r].iter_mut().for_each(|x| *x = std::cmp::min(*x, y));
+                    }
+                    1 => {
+                        st.chmax(l..r, y);
+                        xs[l..r].iter_mut().for_each(|x| *x = std::cmp::max(*x, y));
+                    }
+                    2 => {
+                        st.add(l..r, y);
+                        xs[l..r].iter_mut().for_each(|x| *x += y);
+                    }
+                    _ => {
+                        st.assign(l..r, y);
+                        xs[l..r].iter_mut().for_each(|x| *x = y);
+                    }
+                }
+                let l = rand.gen::<usize>() % (n + 1);
+                let r = l + rand.gen::<usize>() % (n + 1 - l);
+                assert_eq!(st.sum(l..r), xs[l..r].iter().sum::<i64>());
+                assert_eq!(st.min(l..r), xs[l..r].iter().cloned().min().unwrap_or(INF));
+                assert_eq!(
+                    st.max(l..r),
+                    xs[l..r].iter().cloned().max().unwrap_or(NEG_INF)
+                );
+                assert_eq!(st.to_vec(), xs);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compose() {
+        let f = BeatsAct::add(3) * BeatsAct::chmin(5) * BeatsAct::add(-1) * BeatsAct::chmax(2);
+        for x in -10..10 {
+            let expected = std::cmp::max(std::cmp::min(x + 3, 5) - 1, 2);
+            let node = f.try_act(BeatsNode::new(x)).unwrap();
+            assert_eq!(node.sum, expected);
+        }
+    }
+}
@@ -2,6 +2,7 @@
 use crate::algebra::monoid::*;
 
 pub struct SegmentTree<X> {
+    length: usize,       // of leaves
     length_upper: usize, // power of 2
     size: usize,         // of nodes
     data: Vec<X>,
@@ -21,6 +22,7 @@ impl<X: Copy + Monoid> SegmentTree<X> {
         let size = length_upper * 2 - 1;
         let data = vec![X::one(); size];
         SegmentTree {
+            length,
             length_upper,
             size,
             data,
@@ -67,6 +69,67 @@ impl<X: Copy + Monoid> SegmentTree<X> {
     pub fn product(&self, range: std::ops::Range<usize>) -> X {
         self.product_sub(range, 0, 0..self.length_upper)
     }
+    /// Max r s.t. pred(product(l..r)), with O(log n)
+    /// pred should be monotone and pred(X::one()) == true
+    pub fn max_right<F: Fn(X) -> bool>(&self, l: usize, pred: F) -> usize {
+        assert!(l <= self.length);
+        if l == self.length {
+            return self.length;
+        }
+        // 1-indexed heap; node v is at self.data[v - 1]
+        let mut v = l + self.length_upper;
+        let mut acc = X::one();
+        loop {
+            while v & 1 == 0 {
+                v >>= 1;
+            }
+            if !pred(acc * self.data[v - 1]) {
+                while v < self.length_upper {
+                    v *= 2;
+                    if pred(acc * self.data[v - 1]) {
+                        acc = acc * self.data[v - 1];
+                        v += 1;
+                    }
+                }
+                return v - self.length_upper;
+            }
+            acc = acc * self.data[v - 1];
+            v += 1;
+            if v.is_power_of_two() {
+                return self.length;
+            }
+        }
+    }
+    /// Min l s.t. pred(product(l..r)), with O(log n)
+    /// pred should be monotone and pred(X::one()) == true
+    pub fn min_left<F: Fn(X) -> bool>(&self, r: usize, pred: F) -> usize {
+        assert!(r <= self.length);
+        if r == 0 {
+            return 0;
+        }
+        let mut v = r + self.length_upper;
+        let mut acc = X::one();
+        loop {
+            v -= 1;
+            while v > 1 && v & 1 == 1 {
+                v >>= 1;
+            }
+            if !pred(self.data[v - 1] * acc) {
+                while v < self.length_upper {
+                    v = v * 2 + 1;
+                    if pred(self.data[v - 1] * acc) {
+                        acc = self.data[v - 1] * acc;
+                        v -= 1;
+                    }
+                }
+                return v + 1 - self.length_upper;
+            }
+            acc = self.data[v - 1] * acc;
+            if v.is_power_of_two() {
+                return 0;
+            }
+        }
+    }
 }
 impl<X: std::fmt::Debug> SegmentTree<X> {
     pub fn debug(&self) {
@@ -80,3 +143,31 @@ impl<X: std::fmt::Debug> SegmentTree<X> {
         eprintln!();
     }
 }
+
+#[cfg(test)]
+mod test_segment_tree {
+    use crate::algebra::monoid_sum::*;
+    use crate::sequence::tree::segment_tree::*;
+
+    #[test]
+    fn test_max_right_min_left() {
+        let xs = vec![3, 1, 4, 1, 5, 9, 2, 6, 5];
+        let st = SegmentTree::from(xs.iter().map(|&x| Sum(x)).collect());
+        for k in 0..40 {
+            for l in 0..=xs.len() {
+                let mut r = l;
+                while r < xs.len() && xs[l..=r].iter().sum::<i128>() <= k {
+                    r += 1;
+                }
+                assert_eq!(st.max_right(l, |s| s.0 <= k), r);
+            }
+            for r in 0..=xs.len() {
+                let mut l = r;
+                while l > 0 && xs[l - 1..r].iter().sum::<i128>() <= k {
+                    l -= 1;
+                }
+                assert_eq!(st.min_left(r, |s| s.0 <= k), l);
+            }
+        }
+    }
+}
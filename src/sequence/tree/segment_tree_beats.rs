@@ -0,0 +1,207 @@
+/// Sequence - Segment Tree Beats (Lazy Segment Tree with Failable Acts)
+use crate::algebra::act::*;
+use crate::algebra::monoid::*;
+
+/// When an act fails on a node, it is pushed down to the children and the node is recomputed.
+#[derive(Debug, Clone)]
+pub struct SegmentTreeBeats<X, M> {
+    length: usize,       // of leaves
+    length_upper: usize, // power of 2
+    log: usize,          // length_upper == 2**log
+    data: Vec<X>,        // 1-indexed heap; leaves are data[length_upper..]
+    act: Vec<Option<M>>, // pending acts for children; None is identity
+}
+impl<X: Copy + Monoid, M: Copy + Monoid + FailableAct<X>> SegmentTreeBeats<X, M> {
+    pub fn new(length: usize) -> Self {
+        let mut length_upper = 1;
+        let mut log = 0;
+        while length_upper < length {
+            length_upper *= 2;
+            log += 1;
+        }
+        let data = vec![X::one(); length_upper * 2];
+        let act = vec![None; length_upper];
+        SegmentTreeBeats {
+            length,
+            length_upper,
+            log,
+            data,
+            act,
+        }
+    }
+    pub fn from(xs: Vec<X>) -> Self {
+        let mut tree = Self::new(xs.len());
+        for (i, &x) in xs.iter().enumerate() {
+            tree.data[tree.length_upper + i] = x;
+        }
+        for k in (1..tree.length_upper).rev() {
+            tree.pull(k);
+        }
+        tree
+    }
+    fn bounds<R: std::ops::RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        use std::ops::Bound::*;
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r + 1,
+            Excluded(&r) => r,
+            Unbounded => self.length,
+        };
+        assert!(r <= self.length);
+        (l, r)
+    }
+    fn pull(&mut self, k: usize) {
+        self.data[k] = self.data[2 * k] * self.data[2 * k + 1];
+    }
+    fn apply(&mut self, k: usize, m: M) {
+        match m.try_act(self.data[k]) {
+            Some(x) => {
+                self.data[k] = x;
+                if k < self.length_upper {
+                    self.act[k] = Some(compose(self.act[k], m));
+                }
+            }
+            None => {
+                assert!(k < self.length_upper, "Act failed on a leaf");
+                self.act[k] = Some(compose(self.act[k], m));
+                self.push(k);
+                self.pull(k);
+            }
+        }
+    }
+    fn push(&mut self, k: usize) {
+        if let Some(m) = self.act[k].take() {
+            self.apply(2 * k, m);
+            self.apply(2 * k + 1, m);
+        }
+    }
+    pub fn get(&self, i: usize) -> X {
+        assert!(i < self.length);
+        let mut k = i + self.length_upper;
+        let mut x = self.data[k];
+        while k > 1 {
+            k >>= 1;
+            if let Some(m) = self.act[k] {
+                x = m.try_act(x).expect("Act failed on a leaf");
+            }
+        }
+        x
+    }
+    pub fn set(&mut self, i: usize, x: X) {
+        assert!(i < self.length);
+        let k = i + self.length_upper;
+        for h in (1..=self.log).rev() {
+            self.push(k >> h);
+        }
+        self.data[k] = x;
+        for h in 1..=self.log {
+            self.pull(k >> h);
+        }
+    }
+    pub fn update<R: std::ops::RangeBounds<usize>>(&mut self, range: R, m: M) {
+        let (l, r) = self.bounds(range);
+        if l >= r {
+            return;
+        }
+        let (l, r) = (l + self.length_upper, r + self.length_upper);
+        for h in (1..=self.log).rev() {
+            if ((l >> h) << h) != l {
+                self.push(l >> h);
+            }
+            if ((r >> h) << h) != r {
+                self.push((r - 1) >> h);
+            }
+        }
+        {
+            let (mut l, mut r) = (l, r);
+            while l < r {
+                if l & 1 == 1 {
+                    self.apply(l, m);
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    self.apply(r, m);
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+        for h in 1..=self.log {
+            if ((l >> h) << h) != l {
+                self.pull(l >> h);
+            }
+            if ((r >> h) << h) != r {
+                self.pull((r - 1) >> h);
+            }
+        }
+    }
+    /// Product on range, with pushing acts down
+    pub fn product<R: std::ops::RangeBounds<usize>>(&mut self, range: R) -> X {
+        let (l, r) = self.bounds(range);
+        if l >= r {
+            return X::one();
+        }
+        let (mut l, mut r) = (l + self.length_upper, r + self.length_upper);
+        for h in (1..=self.log).rev() {
+            if ((l >> h) << h) != l {
+                self.push(l >> h);
+            }
+            if ((r >> h) << h) != r {
+                self.push((r - 1) >> h);
+            }
+        }
+        let mut sml = X::one();
+        let mut smr = X::one();
+        while l < r {
+            if l & 1 == 1 {
+                sml = sml * self.data[l];
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                smr = self.data[r] * smr;
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        sml * smr
+    }
+    pub fn all_product(&self) -> X {
+        self.data[1]
+    }
+    pub fn to_vec(&self) -> Vec<X> {
+        (0..self.length).map(|i| self.get(i)).collect()
+    }
+}
+/// m then n
+fn compose<M: Monoid>(m: Option<M>, n: M) -> M {
+    match m {
+        Some(m) => m * n,
+        None => n,
+    }
+}
+
+#[cfg(test)]
+mod test_segment_tree_beats {
+    use crate::algebra::act_add::*;
+    use crate::algebra::monoid_max::*;
+    use crate::sequence::tree::segment_tree_beats::*;
+
+    #[test]
+    fn test_never_failing_act() {
+        let xs: Vec<i64> = vec![3, 1, 4, 1, 5, 9, 2, 6, 5];
+        let mut st: SegmentTreeBeats<MaxInt<i64>, Add<MaxInt<i64>>> =
+            SegmentTreeBeats::from(xs.iter().map(|&x| MaxInt::Val(x)).collect());
+        st.update(2..6, Add(MaxInt::Val(-3)));
+        assert_eq!(st.product(..), MaxInt::Val(6));
+        assert_eq!(st.product(2..6), MaxInt::Val(6));
+        assert_eq!(st.get(5), MaxInt::Val(6));
+        st.set(5, MaxInt::Val(0));
+        assert_eq!(st.product(..=6), MaxInt::Val(3));
+    }
+}
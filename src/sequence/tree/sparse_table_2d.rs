@@ -0,0 +1,81 @@
+/// Sequence - 2D Sparse Table
+use crate::algebra::monoid::*;
+
+/// X は冪等モノイド (x * x == x)
+#[derive(Debug)]
+pub struct SparseTable2D<X: Monoid> {
+    data: Vec<Vec<Vec<Vec<X>>>>, // data[a][b][i][j] is the product of [i, i + 2**a) x [j, j + 2**b)
+}
+impl<X: Monoid + Copy> SparseTable2D<X> {
+    pub fn new(xs: &[Vec<X>]) -> Self {
+        let height = xs.len();
+        let width = if height > 0 { xs[0].len() } else { 0 };
+        let log = |n: usize| (0..64).filter(|i| (1 << i) <= n).count();
+        let (kh, kw) = (log(height), log(width));
+        let mut data = vec![vec![vec![vec![X::one(); width]; height]; kw]; kh];
+        for a in 0..kh {
+            for b in 0..kw {
+                for i in 0..=height - (1 << a) {
+                    for j in 0..=width - (1 << b) {
+                        data[a][b][i][j] = if a > 0 {
+                            data[a - 1][b][i][j] * data[a - 1][b][i + (1 << (a - 1))][j]
+                        } else if b > 0 {
+                            data[a][b - 1][i][j] * data[a][b - 1][i][j + (1 << (b - 1))]
+                        } else {
+                            xs[i][j]
+                        };
+                    }
+                }
+            }
+        }
+        Self { data }
+    }
+    /// Product on the rectangle [top, bottom) x [left, right)
+    pub fn product(&self, is: std::ops::Range<usize>, js: std::ops::Range<usize>) -> X {
+        if is.start >= is.end || js.start >= js.end {
+            return X::one();
+        }
+        let log = |n: usize| (1..64).filter(|i| (1 << i) <= n).count();
+        let a = log(is.end - is.start);
+        let b = log(js.end - js.start);
+        let table = &self.data[a][b];
+        let (i0, i1) = (is.start, is.end - (1 << a));
+        let (j0, j1) = (js.start, js.end - (1 << b));
+        table[i0][j0] * table[i0][j1] * table[i1][j0] * table[i1][j1]
+    }
+}
+
+#[cfg(test)]
+mod test_sparse_table_2d {
+    use crate::algebra::monoid_min::*;
+    use crate::num::random::xorshift::*;
+    use crate::sequence::tree::sparse_table_2d::*;
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for &(h, w) in &[(0, 0), (1, 1), (1, 5), (5, 1), (6, 9)] {
+            let xs: Vec<Vec<MinInt<i64>>> = (0..h)
+                .map(|_| {
+                    (0..w)
+                        .map(|_| MinInt::Val(rand.gen::<i64>() % 100))
+                        .collect()
+                })
+                .collect();
+            let st = SparseTable2D::new(&xs);
+            for top in 0..=h {
+                for bottom in top..=h {
+                    for left in 0..=w {
+                        for right in left..=w {
+                            let expected = xs[top..bottom]
+                                .iter()
+                                .flat_map(|row| row[left..right].iter().cloned())
+                                .product();
+                            assert_eq!(st.product(top..bottom, left..right), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
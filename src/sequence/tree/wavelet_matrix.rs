@@ -0,0 +1,268 @@
+/// Sequence - Wavelet Matrix (on u64)
+use crate::sequence::succinct_bit_vector::*;
+
+/// Static sequence of u64 with O(log max) range queries
+#[derive(Debug, Clone)]
+pub struct WaveletMatrix {
+    length: usize,
+    log: usize,                     // bit length of values
+    levels: Vec<SuccinctBitVector>, // levels[k] for the (log-1-k)-th bit
+    mids: Vec<usize>,               // number of 0s on each level
+}
+impl WaveletMatrix {
+    pub fn from(xs: &[u64]) -> Self {
+        let length = xs.len();
+        let max = xs.iter().max().cloned().unwrap_or(0);
+        let log = 64 - max.leading_zeros() as usize;
+        let mut levels = vec![];
+        let mut mids = vec![];
+        let mut cur = xs.to_vec();
+        for k in 0..log {
+            let bit = log - 1 - k;
+            let bits: Vec<bool> = cur.iter().map(|&x| (x >> bit) & 1 == 1).collect();
+            let (mut zeros, ones): (Vec<u64>, Vec<u64>) =
+                cur.iter().partition(|&&x| (x >> bit) & 1 == 0);
+            levels.push(SuccinctBitVector::from(&bits));
+            mids.push(zeros.len());
+            zeros.extend(ones);
+            cur = zeros;
+        }
+        Self {
+            length,
+            log,
+            levels,
+            mids,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.length
+    }
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+    /// x < 2**log
+    fn fits(&self, x: u64) -> bool {
+        x.checked_shr(self.log as u32).unwrap_or(0) == 0
+    }
+    /// Range on the next level
+    fn down(&self, k: usize, l: usize, r: usize, bit: bool) -> (usize, usize) {
+        let level = &self.levels[k];
+        if bit {
+            (self.mids[k] + level.rank1(l), self.mids[k] + level.rank1(r))
+        } else {
+            (level.rank0(l), level.rank0(r))
+        }
+    }
+    /// self[i]
+    pub fn get(&self, i: usize) -> u64 {
+        assert!(i < self.length);
+        let mut x = 0;
+        let mut i = i;
+        for k in 0..self.log {
+            let bit = self.levels[k].get(i);
+            x = (x << 1) | bit as u64;
+            i = self.down(k, i, i, bit).0;
+        }
+        x
+    }
+    /// k-th (0-indexed) smallest value in xs[range]
+    pub fn kth_smallest(&self, range: std::ops::Range<usize>, k: usize) -> Option<u64> {
+        assert!(range.end <= self.length);
+        if range.start + k >= range.end {
+            return None;
+        }
+        let (mut l, mut r) = (range.start, range.end);
+        let mut k = k;
+        let mut x = 0;
+        for lv in 0..self.log {
+            let zeros = self.levels[lv].rank0(r) - self.levels[lv].rank0(l);
+            let bit = k >= zeros;
+            if bit {
+                k -= zeros;
+            }
+            x = (x << 1) | bit as u64;
+            (l, r) = self.down(lv, l, r, bit);
+        }
+        Some(x)
+    }
+    /// k-th (0-indexed) largest value in xs[range]
+    pub fn kth_largest(&self, range: std::ops::Range<usize>, k: usize) -> Option<u64> {
+        let n = range.end.saturating_sub(range.start);
+        if k >= n {
+            return None;
+        }
+        self.kth_smallest(range, n - 1 - k)
+    }
+    /// Number of values < x in xs[range]
+    pub fn rank_less_than(&self, range: std::ops::Range<usize>, x: u64) -> usize {
+        assert!(range.end <= self.length);
+        if range.start >= range.end {
+            return 0;
+        }
+        if !self.fits(x) {
+            return range.end - range.start;
+        }
+        let (mut l, mut r) = (range.start, range.end);
+        let mut count = 0;
+        for k in 0..self.log {
+            let bit = (x >> (self.log - 1 - k)) & 1 == 1;
+            if bit {
+                count += self.levels[k].rank0(r) - self.levels[k].rank0(l);
+            }
+            (l, r) = self.down(k, l, r, bit);
+        }
+        count
+    }
+    /// Number of values in `values` in xs[range]
+    pub fn range_freq(&self, range: std::ops::Range<usize>, values: std::ops::Range<u64>) -> usize {
+        if values.start >= values.end {
+            return 0;
+        }
+        self.rank_less_than(range.clone(), values.end) - self.rank_less_than(range, values.start)
+    }
+    /// Number of x in xs[range]
+    pub fn count(&self, range: std::ops::Range<usize>, x: u64) -> usize {
+        assert!(range.end <= self.length);
+        if range.start >= range.end || !self.fits(x) {
+            return 0;
+        }
+        let (mut l, mut r) = (range.start, range.end);
+        for k in 0..self.log {
+            (l, r) = self.down(k, l, r, (x >> (self.log - 1 - k)) & 1 == 1);
+        }
+        r - l
+    }
+    /// Position of the k-th (0-indexed) x in xs
+    pub fn select(&self, x: u64, k: usize) -> Option<usize> {
+        if k >= self.count(0..self.length, x) {
+            return None;
+        }
+        let mut l = 0;
+        for lv in 0..self.log {
+            l = self.down(lv, l, l, (x >> (self.log - 1 - lv)) & 1 == 1).0;
+        }
+        let mut i = l + k;
+        for lv in (0..self.log).rev() {
+            i = if (x >> (self.log - 1 - lv)) & 1 == 1 {
+                self.levels[lv].select1(i - self.mids[lv]).unwrap()
+            } else {
+                self.levels[lv].select0(i).unwrap()
+            };
+        }
+        Some(i)
+    }
+    /// Max value < upper in xs[range]
+    pub fn prev_value(&self, range: std::ops::Range<usize>, upper: u64) -> Option<u64> {
+        let count = self.rank_less_than(range.clone(), upper);
+        if count == 0 {
+            None
+        } else {
+            self.kth_smallest(range, count - 1)
+        }
+    }
+    /// Min value >= lower in xs[range]
+    pub fn next_value(&self, range: std::ops::Range<usize>, lower: u64) -> Option<u64> {
+        let count = self.rank_less_than(range.clone(), lower);
+        self.kth_smallest(range, count)
+    }
+    /// Most frequent k values in xs[range] as (value, frequency),
+    /// in the descending order of frequency (and then ascending order of value)
+    pub fn topk(&self, range: std::ops::Range<usize>, k: usize) -> Vec<(u64, usize)> {
+        use std::cmp::Reverse;
+        assert!(range.end <= self.length);
+        let mut result = vec![];
+        let mut heap = std::collections::BinaryHeap::new();
+        if range.start < range.end {
+            // (width, value padded with 0s, level, l)
+            heap.push((range.end - range.start, Reverse(0_u64), 0, range.start));
+        }
+        while let Some((width, Reverse(x), lv, l)) = heap.pop() {
+            if result.len() >= k {
+                break;
+            }
+            if lv == self.log {
+                result.push((x, width));
+                continue;
+            }
+            let r = l + width;
+            for bit in [false, true] {
+                let (l, r) = self.down(lv, l, r, bit);
+                if l < r {
+                    let x = x | ((bit as u64) << (self.log - 1 - lv));
+                    heap.push((r - l, Reverse(x), lv + 1, l));
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test_wavelet_matrix {
+    use crate::num::random::xorshift::*;
+    use crate::sequence::tree::wavelet_matrix::*;
+
+    #[test]
+    fn test_random() {
+        let mut rand = XorShift::new();
+        for &(n, m) in &[
+            (0, 1),
+            (1, 1),
+            (30, 1),
+            (30, 10),
+            (50, 1 << 40),
+            (20, u64::MAX),
+        ] {
+            let xs: Vec<u64> = (0..n).map(|_| rand.gen::<u64>() % m).collect();
+            let wm = WaveletMatrix::from(&xs);
+            assert_eq!(wm.len(), n);
+            for (i, &x) in xs.iter().enumerate() {
+                assert_eq!(wm.get(i), x);
+            }
+            for l in 0..=n {
+                for r in l..=n {
+                    let mut sorted = xs[l..r].to_vec();
+                    sorted.sort();
+                    for k in 0..=sorted.len() {
+                        assert_eq!(wm.kth_smallest(l..r, k), sorted.get(k).cloned());
+                    }
+                    assert_eq!(wm.kth_largest(l..r, 0), sorted.last().cloned());
+                    let x = if n > 0 {
+                        xs[rand.gen::<usize>() % n]
+                    } else {
+                        0
+                    };
+                    for y in [x, x.wrapping_add(1), 0, u64::MAX] {
+                        let less = sorted.iter().filter(|&&z| z < y).count();
+                        assert_eq!(wm.rank_less_than(l..r, y), less);
+                        assert_eq!(
+                            wm.count(l..r, y),
+                            sorted.iter().filter(|&&z| z == y).count()
+                        );
+                        assert_eq!(wm.prev_value(l..r, y), sorted[..less].last().cloned());
+                        assert_eq!(wm.next_value(l..r, y), sorted.get(less).cloned());
+                    }
+                    assert_eq!(
+                        wm.range_freq(l..r, x / 2..x),
+                        sorted.iter().filter(|&&z| x / 2 <= z && z < x).count()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_select_topk() {
+        let xs = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let wm = WaveletMatrix::from(&xs);
+        assert_eq!(wm.select(5, 0), Some(4));
+        assert_eq!(wm.select(5, 2), Some(10));
+        assert_eq!(wm.select(5, 3), None);
+        assert_eq!(wm.select(7, 0), None);
+        assert_eq!(wm.select(100, 0), None);
+        assert_eq!(wm.topk(0..11, 3), vec![(5, 3), (1, 2), (3, 2)]);
+        assert_eq!(wm.topk(5..11, 2), vec![(5, 2), (2, 1)]);
+        assert_eq!(wm.topk(5..5, 2), vec![]);
+        assert_eq!(wm.topk(0..2, 10), vec![(1, 1), (3, 1)]);
+    }
+}